@@ -14,7 +14,9 @@ fn draw_cell(cell: &Cell, graphics: &mut G2d, context: Context, glyphs: &mut Gly
     let border_rect = Rectangle::new(color::BLACK);
     border_rect.draw(border, &context.draw_state, context.transform, graphics);
 
-    if cell.flagged || !cell.cleared {
+    if cell.mark == Mark::Question {
+        draw_cell_info(Some("?"), color::GRAY, x, y, graphics, context, glyphs)
+    } else if cell.is_flagged() || !cell.cleared {
         draw_cell_info(None, color::GRAY, x, y, graphics, context, glyphs)
     } else if cell.is_mine() {
         draw_cell_info(None, color::RED, x, y, graphics, context, glyphs)
@@ -124,7 +126,7 @@ fn main() {
             let flag_scale = (SIZE_FACTOR - 10.0) / flag_texture.get_size().1 as f64;
 
             game.board.for_each_cell(|_, cell, _| {
-                if cell.flagged {
+                if cell.is_flagged() {
                     image(
                         &flag_texture,
                         context
@@ -172,7 +174,7 @@ fn main() {
                         };
                         match button {
                             MouseButton::Left => _ = game.selected_at(coordinates),
-                            MouseButton::Right => game.toggle_flagged(coordinates),
+                            MouseButton::Right => _ = game.cycle_mark(coordinates),
                             _ => {}
                         }
                     }
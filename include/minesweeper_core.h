@@ -0,0 +1,167 @@
+#ifndef MINESWEEPER_CORE_H
+#define MINESWEEPER_CORE_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum MsGameState {
+  MS_GAME_STATE_PLAYING,
+  MS_GAME_STATE_WON,
+  MS_GAME_STATE_LOST,
+  /**
+   * Returned for null handles.
+   */
+  MS_GAME_STATE_INVALID,
+} MsGameState;
+
+typedef enum MsStatus {
+  MS_STATUS_OK,
+  MS_STATUS_NULL_POINTER,
+  MS_STATUS_OUT_OF_BOUNDS,
+  /**
+   * The action did not change anything, like flagging a revealed cell.
+   */
+  MS_STATUS_REJECTED,
+  /**
+   * The game was already won or lost.
+   */
+  MS_STATUS_GAME_OVER,
+} MsStatus;
+
+typedef enum MsCellState {
+  MS_CELL_STATE_HIDDEN,
+  MS_CELL_STATE_FLAGGED,
+  MS_CELL_STATE_QUESTIONED,
+  MS_CELL_STATE_REVEALED,
+  MS_CELL_STATE_MINE,
+  MS_CELL_STATE_VOID,
+} MsCellState;
+
+typedef enum MsDifficulty {
+  MS_DIFFICULTY_EASY,
+  MS_DIFFICULTY_MEDIUM,
+  MS_DIFFICULTY_HARD,
+} MsDifficulty;
+
+/**
+ * Opaque game handle.
+ */
+typedef struct MsGameHandle MsGameHandle;
+
+/**
+ * What the player sees of a cell. `number` is the count of neighbouring
+ * mines for revealed cells and the number of flags for flagged ones.
+ */
+typedef struct MsCellInfo {
+  enum MsCellState state;
+  int8_t number;
+} MsCellInfo;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Game with one of the `MsDifficulty` values. Returns null for any other
+ * value.
+ */
+struct MsGameHandle *ms_game_new(uint32_t difficulty);
+
+/**
+ * Game with `mines` placed from `seed`. Returns null when the board is
+ * empty, has more than 2^20 cells or the mines do not fit.
+ */
+struct MsGameHandle *ms_game_new_custom(size_t width, size_t height, uint32_t mines, uint64_t seed);
+
+/**
+ * Game on a fixed layout in the `Board::from_text` format. Returns null
+ * when the text is not a valid board.
+ *
+ * # Safety
+ *
+ * `text` must be null or a valid NUL-terminated string.
+ */
+struct MsGameHandle *ms_game_from_text(const char *text);
+
+/**
+ * # Safety
+ *
+ * `game` must be null or a handle that was not freed yet.
+ */
+void ms_game_free(struct MsGameHandle *game);
+
+/**
+ * # Safety
+ *
+ * `game` must be null or a live handle.
+ */
+size_t ms_game_width(const struct MsGameHandle *game);
+
+/**
+ * # Safety
+ *
+ * `game` must be null or a live handle.
+ */
+size_t ms_game_height(const struct MsGameHandle *game);
+
+/**
+ * # Safety
+ *
+ * `game` must be null or a live handle.
+ */
+enum MsGameState ms_game_state(const struct MsGameHandle *game);
+
+/**
+ * # Safety
+ *
+ * `game` must be null or a live handle.
+ */
+int32_t ms_game_flags_remaining(const struct MsGameHandle *game);
+
+/**
+ * Rejected for cells already revealed or flagged.
+ *
+ * # Safety
+ *
+ * `game` must be null or a live handle.
+ */
+enum MsStatus ms_game_reveal(struct MsGameHandle *game, size_t x, size_t y);
+
+/**
+ * Toggles a flag on a hidden cell.
+ *
+ * # Safety
+ *
+ * `game` must be null or a live handle.
+ */
+enum MsStatus ms_game_flag(struct MsGameHandle *game, size_t x, size_t y);
+
+/**
+ * # Safety
+ *
+ * `game` must be null or a live handle.
+ */
+enum MsStatus ms_game_chord(struct MsGameHandle *game, size_t x, size_t y);
+
+/**
+ * Writes what the player sees at the coordinates into `cell`.
+ *
+ * # Safety
+ *
+ * `game` must be null or a live handle, and `cell` null or writable.
+ */
+enum MsStatus ms_game_cell(const struct MsGameHandle *game,
+                           size_t x,
+                           size_t y,
+                           struct MsCellInfo *cell);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MINESWEEPER_CORE_H */
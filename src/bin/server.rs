@@ -0,0 +1,12 @@
+use std::env;
+use std::net::TcpListener;
+use std::sync::Arc;
+
+use minesweeper_core::server::Server;
+
+fn main() -> std::io::Result<()> {
+    let address = env::args().nth(1).unwrap_or_else(|| "127.0.0.1:7878".to_string());
+    let listener = TcpListener::bind(&address)?;
+    println!("Listening on {}", listener.local_addr()?);
+    Arc::new(Server::new()).serve(listener)
+}
@@ -0,0 +1,214 @@
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+
+use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, execute, queue};
+use minesweeper_core::*;
+
+const DIFFICULTIES: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+/// Rows above the board, used by the status line.
+const BOARD_TOP: u16 = 2;
+/// Each cell takes this many terminal columns.
+const CELL_WIDTH: u16 = 2;
+
+enum Screen {
+    Menu { selected: usize },
+    Playing { game: Box<Game>, cursor: Point },
+}
+
+fn main() -> io::Result<()> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, cursor::Hide)?;
+
+    let result = run(&mut stdout);
+
+    execute!(stdout, cursor::Show, DisableMouseCapture, LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn run(stdout: &mut Stdout) -> io::Result<()> {
+    let mut screen = Screen::Menu { selected: 0 };
+    loop {
+        draw(stdout, &screen)?;
+
+        // Poll with a timeout so the timer keeps moving without input.
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let event = event::read()?;
+        if let Event::Key(KeyEvent {
+            code: KeyCode::Char('q') | KeyCode::Esc,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        {
+            return Ok(());
+        }
+        screen = match screen {
+            Screen::Menu { selected } => menu_input(selected, event),
+            Screen::Playing { mut game, cursor } => {
+                let cursor = game_input(&mut game, cursor, event);
+                match cursor {
+                    Some(cursor) => Screen::Playing { game, cursor },
+                    None => Screen::Menu { selected: 0 },
+                }
+            }
+        };
+    }
+}
+
+fn menu_input(selected: usize, event: Event) -> Screen {
+    let Event::Key(KeyEvent {
+        code,
+        kind: KeyEventKind::Press,
+        ..
+    }) = event
+    else {
+        return Screen::Menu { selected };
+    };
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => Screen::Menu {
+            selected: selected.saturating_sub(1),
+        },
+        KeyCode::Down | KeyCode::Char('j') => Screen::Menu {
+            selected: (selected + 1).min(DIFFICULTIES.len() - 1),
+        },
+        KeyCode::Enter | KeyCode::Char(' ') => Screen::Playing {
+            game: Box::new(Game::new(DIFFICULTIES[selected].clone())),
+            cursor: Point::zero(),
+        },
+        _ => Screen::Menu { selected },
+    }
+}
+
+/// Returns the new cursor, or None to go back to the menu.
+fn game_input(game: &mut Game, mut cursor: Point, event: Event) -> Option<Point> {
+    let size = game.board.get_size();
+    match event {
+        Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) => match code {
+            KeyCode::Up | KeyCode::Char('k') => cursor.x = cursor.x.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => cursor.x = (cursor.x + 1).min(size.width - 1),
+            KeyCode::Left | KeyCode::Char('h') => cursor.y = cursor.y.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => cursor.y = (cursor.y + 1).min(size.height - 1),
+            KeyCode::Enter | KeyCode::Char(' ') => play(game, cursor, MouseButton::Left),
+            KeyCode::Char('f') => play(game, cursor, MouseButton::Right),
+            KeyCode::Char('c') => play(game, cursor, MouseButton::Middle),
+            KeyCode::Char('n') => return None,
+            _ => {}
+        },
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(button),
+            column,
+            row,
+            ..
+        }) if row >= BOARD_TOP => {
+            let point = Point {
+                x: (row - BOARD_TOP) as usize,
+                y: (column / CELL_WIDTH) as usize,
+            };
+            if game.board.contains(point) {
+                cursor = point;
+                play(game, cursor, button);
+            }
+        }
+        _ => {}
+    }
+    Some(cursor)
+}
+
+fn play(game: &mut Game, coordinates: Point, button: MouseButton) {
+    if game.is_game_over() || game.is_win() {
+        return;
+    }
+    match button {
+        MouseButton::Left => _ = game.selected_at(coordinates),
+        MouseButton::Right => _ = game.cycle_mark(coordinates),
+        MouseButton::Middle => _ = game.chord_at(coordinates),
+    }
+}
+
+fn draw(stdout: &mut Stdout, screen: &Screen) -> io::Result<()> {
+    queue!(stdout, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+    match screen {
+        Screen::Menu { selected } => draw_menu(stdout, *selected)?,
+        Screen::Playing { game, cursor } => draw_game(stdout, game, *cursor)?,
+    }
+    stdout.flush()
+}
+
+fn draw_menu(stdout: &mut Stdout, selected: usize) -> io::Result<()> {
+    queue!(stdout, Print("Minesweeper"), cursor::MoveToNextLine(2))?;
+    for (index, difficulty) in DIFFICULTIES.iter().enumerate() {
+        if index == selected {
+            queue!(stdout, SetAttribute(Attribute::Reverse))?;
+        }
+        queue!(stdout, Print(format!(" {difficulty} ")), SetAttribute(Attribute::Reset), cursor::MoveToNextLine(1))?;
+    }
+    queue!(stdout, cursor::MoveToNextLine(1), Print("up/down: choose, enter: play, q: quit"))
+}
+
+fn draw_game(stdout: &mut Stdout, game: &Game, cursor: Point) -> io::Result<()> {
+    let status = if game.is_game_over() {
+        "You lost"
+    } else if game.is_win() {
+        "You won!"
+    } else {
+        ""
+    };
+    queue!(
+        stdout,
+        Print(format!("Mines: {}  Time: {}s  {}", game.flags_remaining(), game.elapsed().as_secs(), status)),
+        cursor::MoveTo(0, BOARD_TOP)
+    )?;
+
+    let view = game.player_view();
+    for (x, row) in view.rows().enumerate() {
+        for (y, cell) in row.iter().enumerate() {
+            let (glyph, color) = glyph(*cell);
+            if (Point { x, y }) == cursor {
+                queue!(stdout, SetAttribute(Attribute::Reverse))?;
+            }
+            queue!(stdout, SetForegroundColor(color), Print(format!("{glyph:>2}")), ResetColor, SetAttribute(Attribute::Reset))?;
+        }
+        queue!(stdout, cursor::MoveToNextLine(1))?;
+    }
+
+    queue!(
+        stdout,
+        cursor::MoveToNextLine(1),
+        Print("arrows: move, space: reveal, f: flag, c: chord, mouse: left/right/middle, n: menu, q: quit")
+    )
+}
+
+fn glyph(cell: VisibleCell) -> (String, Color) {
+    match cell {
+        VisibleCell::Hidden => ("#".to_string(), Color::DarkGrey),
+        VisibleCell::Flagged(_) => ("F".to_string(), Color::Red),
+        VisibleCell::Questioned => ("?".to_string(), Color::Yellow),
+        VisibleCell::Mine => ("*".to_string(), Color::Red),
+        VisibleCell::Void => (" ".to_string(), Color::Reset),
+        VisibleCell::Revealed(0) => (".".to_string(), Color::Grey),
+        VisibleCell::Revealed(number) => (number.to_string(), number_color(number)),
+    }
+}
+
+fn number_color(number: i8) -> Color {
+    match number {
+        1 => Color::Blue,
+        2 => Color::Green,
+        3 => Color::Red,
+        4 => Color::DarkBlue,
+        5 => Color::DarkRed,
+        6 => Color::Cyan,
+        7 => Color::Magenta,
+        _ => Color::White,
+    }
+}
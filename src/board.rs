@@ -1,11 +1,26 @@
+use std::sync::Arc;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
 use crate::graphics::*;
+use crate::topology::{Cubic, Edges, Square, Topology};
+use crate::view::PlayerView;
 use crate::Cell;
 
 #[derive(Debug, Clone)]
 pub struct Board {
     pub cells: Vec2<Cell>,
+    pub topology: Arc<dyn Topology>,
+    pub edges: Edges,
+    /// Cells set to `false` are not part of the board.
+    pub mask: Option<Vec2<bool>>,
+    pub mines_per_cell: u8,
 }
 
+/// Largest mine count of a cell that `from_text` can read.
+const MAX_MINES_PER_CELL: u8 = 9;
+
 impl Board {
     // INIT:
 
@@ -25,36 +40,171 @@ impl Board {
         Board::new_with_cells(Vec2 { data: cells })
     }
 
+    pub fn new_empty_with_mask(mask: Vec2<bool>) -> Self {
+        let size = Size {
+            width: mask.get_width(),
+            height: mask.get_height(),
+        };
+        Board::new_empty(size).with_mask(mask)
+    }
+
+    /// Parses a board layout, one line per row: `*` for mines, `.` for
+    /// safe cells and `#` for cells outside of the board. Cells with
+    /// several mines are written as a digit from 2 to 9.
+    pub fn from_text(text: &str) -> Option<Board> {
+        let lines: Vec<&str> = text.lines().map(|line| line.trim()).filter(|line| !line.is_empty()).collect();
+        let height = lines.first()?.chars().count();
+
+        let mut mines = vec![];
+        let mut mask = vec![];
+        for (x, line) in lines.iter().enumerate() {
+            if line.chars().count() != height {
+                return None;
+            }
+            let mut row = vec![];
+            for (y, char) in line.chars().enumerate() {
+                match char {
+                    '*' => mines.push((Point { x, y }, 1)),
+                    '2'..='9' => mines.push((Point { x, y }, char.to_digit(10)? as u8)),
+                    '.' | '#' => {}
+                    _ => return None,
+                }
+                row.push(char != '#');
+            }
+            mask.push(row);
+        }
+
+        let mines_per_cell = mines.iter().map(|(_, mines)| *mines).max().unwrap_or(1);
+        let mut board = Board::new_empty_with_mask(Vec2 { data: mask }).with_mines_per_cell(mines_per_cell);
+        mines.into_iter().for_each(|(point, mines)| {
+            board.replace_cell(Cell { mines, ..Cell::new_mine(point) }, point);
+        });
+        board.add_cell_numbers();
+        Some(board)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        self.cells.for_each_element(|point, cell, _| {
+            if point.x > 0 && point.y == 0 {
+                text.push('\n');
+            }
+            if !self.contains(point) {
+                text.push('#');
+            } else if cell.mines > 1 {
+                text.push_str(&cell.mines.to_string());
+            } else if cell.is_mine() {
+                text.push('*');
+            } else {
+                text.push('.');
+            }
+        });
+        text
+    }
+
+    /// Panics when the mines do not fit in the board, like every
+    /// constructor taking a mine count.
     pub fn new(mines: i32, size: Size) -> Self {
+        Board::new_with_topology(mines, size, Square)
+    }
+
+    pub fn new_with_rng(mines: i32, size: Size, rng: &mut impl Rng) -> Self {
         let mut board = Board::new_empty(size);
-        board.add_mines(mines).add_cell_numbers();
+        board.add_mines_with_rng(mines, rng).expect("the mines do not fit in the board").add_cell_numbers();
         board
     }
 
-    pub fn new_with_cells(cells: Vec2<Cell>) -> Board {
-        Board { cells }
+    pub fn new_with_topology(mines: i32, size: Size, topology: impl Topology + 'static) -> Self {
+        let mut board = Board::new_empty(size).with_topology(topology);
+        board.add_mines(mines).expect("the mines do not fit in the board").add_cell_numbers();
+        board
     }
 
-    // Populate cells:
+    /// Three dimensional board, see `Cubic` for how the layers are stored.
+    pub fn new_cubic(mines: i32, size: Size3) -> Self {
+        Board::new_with_topology(mines, size.flatten(), Cubic { depth: size.depth })
+    }
+
+    /// Three dimensional board from its layers. Cell coordinates are
+    /// updated to the flattened positions.
+    pub fn new_with_layers(cells: Vec3<Cell>) -> Board {
+        let depth = cells.get_depth();
+        let mut cells = cells.flatten();
+        cells.for_each_element_mut(|point, cell, _| cell.coordinates = point);
+        Board::new_with_cells(cells).with_topology(Cubic { depth })
+    }
 
-    fn add_mines(&mut self, mines: i32) -> &mut Self {
-        if mines == 0 {
-            return self;
+    pub fn new_with_cells(cells: Vec2<Cell>) -> Board {
+        Board {
+            cells,
+            topology: Arc::new(Square),
+            edges: Edges::Bounded,
+            mask: None,
+            mines_per_cell: 1,
         }
-        let coordinates = Point::random_between(0..self.get_width(), 0..self.get_height());
+    }
 
-        let Some(cell) = self.cell_at(coordinates) else {
-            return self.add_mines(mines);
-        };
+    pub fn with_topology(self, topology: impl Topology + 'static) -> Self {
+        Board {
+            topology: Arc::new(topology),
+            ..self
+        }
+    }
+
+    pub fn with_edges(self, edges: Edges) -> Self {
+        Board { edges, ..self }
+    }
+
+    pub fn with_mask(self, mask: Vec2<bool>) -> Self {
+        Board { mask: Some(mask), ..self }
+    }
 
-        if cell.is_mine() {
-            return self.add_mines(mines);
+    /// Allows `add_mines` to place up to `mines_per_cell` mines in a cell.
+    /// See `max_mines_per_cell` for the largest value it accepts.
+    pub fn with_mines_per_cell(self, mines_per_cell: u8) -> Self {
+        Board { mines_per_cell, ..self }
+    }
+
+    // Populate cells:
+
+    pub fn add_mines(&mut self, mines: i32) -> Option<&mut Self> {
+        self.add_mines_with_rng(mines, &mut rand::thread_rng())
+    }
+
+    /// Like `add_mines`, drawing positions from `rng` so the layout can be
+    /// seeded. Returns `None`, without placing any mine, when they do not
+    /// fit in the free cells or `mines_per_cell` is above
+    /// `max_mines_per_cell`.
+    pub fn add_mines_with_rng(&mut self, mines: i32, rng: &mut impl Rng) -> Option<&mut Self> {
+        if self.mines_per_cell > self.max_mines_per_cell() {
+            return None;
         }
+        let mut slots = vec![];
+        self.for_each_cell(|point, cell, _| {
+            let free = self.mines_per_cell.saturating_sub(cell.mines) as usize;
+            slots.extend(std::iter::repeat_n(point, free));
+        });
 
-        let mine = Cell::new_mine(coordinates);
-        self.replace_cell(mine, mine.coordinates);
+        let mines = usize::try_from(mines).ok().filter(|mines| *mines <= slots.len())?;
+        let (chosen, _) = slots.partial_shuffle(rng, mines);
+        for &coordinates in chosen.iter() {
+            let mines = self.cells.get_element(coordinates).map_or(0, |cell| cell.mines);
+            let mine = Cell {
+                mines: mines + 1,
+                ..Cell::new_mine(coordinates)
+            };
+            self.replace_cell(mine, coordinates);
+        }
+        Some(self)
+    }
 
-        self.add_mines(mines - 1)
+    /// Most mines a cell of this board can hold: 9, the largest count
+    /// `from_text` reads, or fewer when the topology has so many neighbours
+    /// that the numbers would not fit in `Cell::number`.
+    pub fn max_mines_per_cell(&self) -> u8 {
+        let mut neighbours = 1;
+        self.for_each_cell(|point, _, _| neighbours = neighbours.max(self.get_cells_around(point).len()));
+        MAX_MINES_PER_CELL.min((i8::MAX as usize / neighbours) as u8)
     }
 
     pub fn add_cell_numbers(&mut self) -> &mut Self {
@@ -68,13 +218,8 @@ impl Board {
 
     fn count_mines_around_cell_at(&mut self, coordinates: Point) {
         let count_mines = || {
-            let mut count: i8 = 0;
-            self.get_cells_around(coordinates).iter().for_each(|cell| {
-                if cell.is_mine() {
-                    count += 1;
-                }
-            });
-            count
+            let count: u32 = self.get_cells_around(coordinates).iter().map(|cell| cell.mines as u32).sum();
+            i8::try_from(count).unwrap_or(i8::MAX)
         };
 
         self.replace_cell(
@@ -90,9 +235,24 @@ impl Board {
     // PUBLIC:
 
     pub fn cell_at(&self, coordinates: Point) -> Option<&Cell> {
+        if !self.contains(coordinates) {
+            return None;
+        }
         self.cells.get_element(coordinates)
     }
 
+    /// Whether the coordinates are inside the board and not masked out.
+    pub fn contains(&self, coordinates: Point) -> bool {
+        Board::is_in_mask(&self.mask, coordinates) && coordinates.x < self.get_width() && coordinates.y < self.get_height()
+    }
+
+    fn is_in_mask(mask: &Option<Vec2<bool>>, coordinates: Point) -> bool {
+        match mask {
+            Some(mask) => mask.get_element(coordinates).copied().unwrap_or(false),
+            None => true,
+        }
+    }
+
     pub fn get_width(&self) -> usize {
         self.cells.get_width()
     }
@@ -108,43 +268,74 @@ impl Board {
         }
     }
 
+    pub fn player_view(&self) -> PlayerView {
+        PlayerView::new(self)
+    }
+
     pub fn replace_cell(&mut self, new_cell: Cell, coordinates: Point) {
         self.cells.replace_at(new_cell, coordinates);
     }
 
-    pub fn for_each_cell(&self, f: impl FnMut(Point, &Cell, &mut bool)) {
-        self.cells.for_each_element(f);
+    pub fn for_each_cell(&self, mut f: impl FnMut(Point, &Cell, &mut bool)) {
+        self.cells.for_each_element(|point, cell, stop| {
+            if self.contains(point) {
+                f(point, cell, stop);
+            }
+        });
     }
 
-    pub fn for_each_cell_mut(&mut self, f: impl FnMut(Point, &mut Cell, &mut bool)) {
-        self.cells.for_each_element_mut(f);
+    pub fn for_each_cell_mut(&mut self, mut f: impl FnMut(Point, &mut Cell, &mut bool)) {
+        let mask = &self.mask;
+        self.cells.for_each_element_mut(|point, cell, stop| {
+            if Board::is_in_mask(mask, point) {
+                f(point, cell, stop);
+            }
+        });
     }
 
     pub fn get_cells_around(&self, coordinates: Point) -> Vec<&Cell> {
-        let (x, y) = (coordinates.x, coordinates.y);
-        let mut cells: Vec<&Cell> = vec![];
+        self.topology
+            .neighbours(coordinates, self.get_size(), self.edges)
+            .into_iter()
+            .filter_map(|point| self.cell_at(point))
+            .collect()
+    }
+
+    /// Minimum number of left clicks needed to clear the board (3BV).
+    pub fn bbbv(&self) -> u32 {
+        let mut bbbv = 0;
+        let mut visited = vec![vec![false; self.get_height()]; self.get_width()];
 
-        for dx in [-1i32, 0, 1] {
-            for dy in [-1i32, 0, 1] {
-                if x as i32 + dx < 0 || y as i32 + dy < 0 || (dx == 0 && dy == 0) {
+        self.for_each_cell(|point, cell, _| {
+            if cell.number != 0 || cell.is_mine() || visited[point.x][point.y] {
+                return;
+            }
+            bbbv += 1;
+            let mut stack = vec![point];
+            while let Some(point) = stack.pop() {
+                if visited[point.x][point.y] {
                     continue;
                 }
-                let coordinates = Point {
-                    x: (x as i32 + dx) as usize,
-                    y: (y as i32 + dy) as usize,
-                };
-                if let Some(cell) = self.cell_at(coordinates) {
-                    cells.push(cell);
+                visited[point.x][point.y] = true;
+                if self.cell_at(point).is_some_and(|cell| cell.number == 0) {
+                    self.get_cells_around(point).iter().for_each(|cell| stack.push(cell.coordinates));
                 }
             }
-        }
-        cells
+        });
+
+        self.for_each_cell(|point, cell, _| {
+            if !cell.is_mine() && !visited[point.x][point.y] {
+                bbbv += 1;
+            }
+        });
+        bbbv
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::topology::{Hexagonal, Neighbourhood};
 
     #[test]
     fn test_top_left_cell() {
@@ -194,6 +385,250 @@ mod tests {
         assert_eq!(cells.len(), 3);
     }
 
+    #[test]
+    fn test_bbbv() {
+        let mut board = Board::new_empty(Size { width: 4, height: 4 });
+        let mine_coordinates = Point { x: 0, y: 3 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+
+        assert_eq!(board.bbbv(), 1);
+
+        let mine_coordinates = Point { x: 3, y: 0 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+
+        assert_eq!(board.bbbv(), 1);
+
+        let mine_coordinates = Point { x: 1, y: 1 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+
+        // One opening at the bottom-right corner plus five numbers it does not reach.
+        assert_eq!(board.bbbv(), 6);
+    }
+
+    #[test]
+    fn test_hexagonal_cell_numbers() {
+        let mut board = Board::new_empty(Size { width: 3, height: 3 }).with_topology(Hexagonal);
+        let mine_coordinates = Point { x: 1, y: 1 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+
+        // Row 1 is shifted right, so (0, 0) and (2, 0) do not touch (1, 1).
+        assert_eq!(board.cell_at(Point { x: 0, y: 0 }).unwrap().number, 0);
+        assert_eq!(board.cell_at(Point { x: 2, y: 0 }).unwrap().number, 0);
+        assert_eq!(board.cell_at(Point { x: 0, y: 2 }).unwrap().number, 1);
+        assert_eq!(board.cell_at(Point { x: 1, y: 0 }).unwrap().number, 1);
+        assert_eq!(board.get_cells_around(mine_coordinates).len(), 6);
+    }
+
+    #[test]
+    fn test_wrapping_cell_numbers() {
+        let mut board = Board::new_empty(Size { width: 4, height: 4 }).with_edges(Edges::Wrapping);
+        let mine_coordinates = Point { x: 0, y: 0 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+
+        assert_eq!(board.get_cells_around(Point { x: 3, y: 3 }).len(), 8);
+        assert_eq!(board.cell_at(Point { x: 3, y: 3 }).unwrap().number, 1);
+        assert_eq!(board.cell_at(Point { x: 0, y: 3 }).unwrap().number, 1);
+        assert_eq!(board.cell_at(Point { x: 2, y: 2 }).unwrap().number, 0);
+    }
+
+    #[test]
+    fn test_wrapping_mines_count() {
+        let mut board = Board::new_empty(Size { width: 5, height: 5 }).with_edges(Edges::Wrapping);
+        board.add_mines(6).unwrap().add_cell_numbers();
+
+        let mut numbers = 0;
+        board.for_each_cell(|_, cell, _| {
+            if !cell.is_mine() {
+                numbers += cell.number as i32;
+            }
+        });
+        let mut mines_around = 0;
+        board.for_each_cell(|point, cell, _| {
+            if cell.is_mine() {
+                mines_around += board.get_cells_around(point).iter().filter(|cell| !cell.is_mine()).count() as i32;
+            }
+        });
+        assert_eq!(numbers, mines_around);
+    }
+
+    #[test]
+    fn test_neighbourhood_cell_numbers() {
+        let size = Size { width: 5, height: 5 };
+        let mut board = Board::new_empty(size).with_topology(Neighbourhood::radius(2));
+        board.for_each_cell_mut(|point, cell, _| {
+            if point != (Point { x: 2, y: 2 }) {
+                *cell = Cell::new_mine(point);
+            }
+        });
+        board.add_cell_numbers();
+
+        assert_eq!(board.cell_at(Point { x: 2, y: 2 }).unwrap().number, 24);
+
+        let mut board = Board::new_empty(size).with_topology(Neighbourhood::knight());
+        let mine_coordinates = Point { x: 2, y: 2 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+
+        assert_eq!(board.cell_at(Point { x: 0, y: 1 }).unwrap().number, 1);
+        assert_eq!(board.cell_at(Point { x: 1, y: 1 }).unwrap().number, 0);
+    }
+
+    #[test]
+    fn test_text_format() {
+        let text = "..*\n#..\n..#";
+        let board = Board::from_text(text).unwrap();
+
+        assert_eq!(board.get_size(), Size { width: 3, height: 3 });
+        assert!(board.cell_at(Point { x: 0, y: 2 }).unwrap().is_mine());
+        assert_eq!(board.cell_at(Point { x: 1, y: 2 }).unwrap().number, 1);
+        assert!(board.cell_at(Point { x: 1, y: 0 }).is_none());
+        assert_eq!(board.to_text(), text);
+
+        assert!(Board::from_text("..\n...").is_none());
+        assert!(Board::from_text("..x").is_none());
+        assert!(Board::from_text("").is_none());
+    }
+
+    #[test]
+    fn test_masked_cells() {
+        let board = Board::from_text(
+            "
+            *.*
+            .#.
+            *.*
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(board.get_cells_around(Point { x: 0, y: 1 }).len(), 4);
+        assert_eq!(board.cell_at(Point { x: 0, y: 1 }).unwrap().number, 2);
+
+        let mut count = 0;
+        board.for_each_cell(|point, _, _| {
+            assert_ne!(point, Point { x: 1, y: 1 });
+            count += 1;
+        });
+        assert_eq!(count, 8);
+    }
+
+    #[test]
+    fn test_masked_cells_are_never_mines() {
+        let mut mask = vec![vec![true; 4]; 4];
+        mask[1][1] = false;
+        mask[2][2] = false;
+        let mut board = Board::new_empty_with_mask(Vec2 { data: mask });
+        board.add_mines(14).unwrap().add_cell_numbers();
+
+        assert!(!board.cells.data[1][1].is_mine());
+        assert!(!board.cells.data[2][2].is_mine());
+        let mut mines = 0;
+        board.for_each_cell(|_, cell, _| {
+            if cell.is_mine() {
+                mines += 1;
+            }
+        });
+        assert_eq!(mines, 14);
+    }
+
+    #[test]
+    fn test_cubic_cell_numbers() {
+        let size = Size3 { width: 3, height: 3, depth: 3 };
+        let mut board = Board::new_cubic(0, size);
+        let mine_coordinates = Point3 { x: 1, y: 1, z: 1 }.flatten(size);
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+
+        assert_eq!(board.get_size(), Size { width: 9, height: 3 });
+        assert_eq!(board.get_cells_around(mine_coordinates).len(), 26);
+        board.for_each_cell(|_, cell, _| {
+            assert!(cell.is_mine() || cell.number == 1);
+        });
+
+        let layer = Board::new_empty(Size { width: 3, height: 3 }).cells;
+        let mut layers = Vec3 {
+            data: vec![layer.clone(), layer.clone(), layer],
+        };
+        layers.data[2].replace_at(Cell::new_mine(Point::zero()), Point::zero());
+        let mut board = Board::new_with_layers(layers);
+        board.add_cell_numbers();
+
+        let mine_coordinates = Point3 { x: 0, y: 0, z: 2 }.flatten(size);
+        assert!(board.cell_at(mine_coordinates).unwrap().is_mine());
+        assert_eq!(board.cell_at(mine_coordinates).unwrap().coordinates, mine_coordinates);
+        assert_eq!(board.cell_at(Point3 { x: 1, y: 1, z: 1 }.flatten(size)).unwrap().number, 1);
+        assert_eq!(board.cell_at(Point3 { x: 1, y: 1, z: 0 }.flatten(size)).unwrap().number, 0);
+
+        let board = Board::new_cubic(20, size);
+        let mut mines = 0;
+        board.for_each_cell(|_, cell, _| {
+            if cell.is_mine() {
+                mines += 1;
+            }
+        });
+        assert_eq!(mines, 20);
+    }
+
+    #[test]
+    fn test_multi_mine_cells() {
+        let board = Board::from_text("3..\n...\n..*").unwrap();
+
+        assert_eq!(board.mines_per_cell, 3);
+        assert_eq!(board.cell_at(Point::zero()).unwrap().mines, 3);
+        assert_eq!(board.cell_at(Point { x: 1, y: 1 }).unwrap().number, 4);
+        assert_eq!(board.cell_at(Point { x: 0, y: 1 }).unwrap().number, 3);
+        assert_eq!(board.cell_at(Point { x: 2, y: 1 }).unwrap().number, 1);
+        assert_eq!(board.to_text(), "3..\n...\n..*");
+
+        let mut board = Board::new_empty(Size { width: 2, height: 2 }).with_mines_per_cell(3);
+        board.add_mines(12).unwrap().add_cell_numbers();
+        board.for_each_cell(|_, cell, _| assert_eq!(cell.mines, 3));
+    }
+
+    #[test]
+    fn test_add_mines_on_dense_boards() {
+        let mut board = Board::new_empty(Size { width: 300, height: 300 });
+        board.add_mines(300 * 300 - 1).unwrap().add_cell_numbers();
+        let mut mines = 0;
+        board.for_each_cell(|_, cell, _| mines += cell.mines as i32);
+        assert_eq!(mines, 300 * 300 - 1);
+
+        let mut board = Board::new_empty(Size { width: 3, height: 3 }).with_mines_per_cell(2);
+        assert!(board.add_mines(19).is_none());
+        assert!(board.add_mines(-1).is_none());
+        board.for_each_cell(|_, cell, _| assert!(!cell.is_mine()));
+        board.add_mines(18).unwrap();
+        board.for_each_cell(|_, cell, _| assert_eq!(cell.mines, 2));
+    }
+
+    #[test]
+    fn test_mines_per_cell_fits_cell_numbers() {
+        let size = Size { width: 5, height: 5 };
+        let mut board = Board::new_empty(size).with_topology(Neighbourhood::radius(2)).with_mines_per_cell(6);
+        assert_eq!(board.max_mines_per_cell(), 5);
+        assert!(board.add_mines(1).is_none());
+        let mut board = board.with_mines_per_cell(5);
+        board.add_mines(25 * 5).unwrap().add_cell_numbers();
+        board.for_each_cell(|_, cell, _| assert_eq!(cell.mines, 5));
+
+        let mut board = Board::new_empty(size).with_mines_per_cell(10);
+        assert_eq!(board.max_mines_per_cell(), 9);
+        assert!(board.add_mines(1).is_none());
+
+        let mut board = Board::new_empty(size).with_topology(Neighbourhood::radius(2));
+        board.for_each_cell_mut(|point, cell, _| {
+            if point != (Point { x: 2, y: 2 }) {
+                *cell = Cell { mines: 9, ..Cell::new_mine(point) };
+            }
+        });
+        board.add_cell_numbers();
+        assert_eq!(board.cell_at(Point { x: 2, y: 2 }).unwrap().number, i8::MAX);
+    }
+
     /// Get a board where the top-left cell must be a number.
     fn get_board_with_number_top_left() -> Board {
         let board = Board::new(
@@ -1,21 +1,42 @@
 use crate::graphics::Point;
 
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Mark {
+    #[default]
+    None,
+    /// Number of flags placed, more than one only on multi-mine boards.
+    Flag(u8),
+    Question,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub struct Cell {
     pub number: i8,
+    pub mines: u8,
     pub cleared: bool,
-    pub flagged: bool,
+    pub mark: Mark,
     pub coordinates: Point,
 }
 
 impl Cell {
     pub fn is_mine(&self) -> bool {
-        self.number.is_negative()
+        self.mines > 0
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.flags() > 0
+    }
+
+    pub fn flags(&self) -> u8 {
+        match self.mark {
+            Mark::Flag(flags) => flags,
+            _ => 0,
+        }
     }
 
     pub fn new_mine(coordinates: Point) -> Cell {
         Cell {
-            number: -1,
+            mines: 1,
             coordinates,
             ..Default::default()
         }
@@ -0,0 +1,40 @@
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Source of time for a game, so timed modes can be tested.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Time elapsed since a fixed, arbitrary origin.
+    fn now(&self) -> Duration;
+}
+
+/// Clock starting on its first reading, so creating one never touches the
+/// system time on platforms that lack it.
+#[derive(Debug, Default)]
+pub struct SystemClock {
+    origin: OnceLock<Instant>,
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.get_or_init(Instant::now).elapsed()
+    }
+}
+
+/// Clock that only moves when told to.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Mutex<Duration>,
+}
+
+impl ManualClock {
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.now.lock().unwrap()
+    }
+}
@@ -0,0 +1,424 @@
+use crate::board::Board;
+use crate::cell::Mark;
+use crate::graphics::*;
+use crate::topology::{topology_from_text, Edges};
+
+/// Version written in the first byte by `Board::to_bytes`.
+pub const ENCODING_VERSION: u8 = 1;
+
+const HAS_MASK: u8 = 1;
+const HAS_STATE: u8 = 1 << 1;
+const HAS_TOPOLOGY: u8 = 1 << 2;
+const WRAPPING: u8 = 1 << 3;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Board {
+    /// Packs the board into bytes: a header with the size, then one bit per
+    /// cell for the mask and mines, and reveal and mark bitmaps only when
+    /// some cell is revealed or marked. Numbers are recomputed on decoding.
+    /// Returns `None` for boards that `from_bytes` would not read back, like
+    /// topologies without `Topology::to_text`.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        if !self.can_encode() {
+            return None;
+        }
+        let cells: Vec<_> = self.cells.data.iter().flatten().collect();
+        let has_state = cells.iter().any(|cell| cell.cleared || cell.mark != Mark::None);
+        let topology = Some(self.topology.to_text()?).filter(|topology| topology != "square");
+        let largest = cells.iter().map(|cell| cell.mines.max(cell.flags())).fold(self.mines_per_cell, u8::max);
+        let bits = bit_width(largest);
+
+        let mut flags = 0;
+        for (enabled, flag) in [
+            (self.mask.is_some(), HAS_MASK),
+            (has_state, HAS_STATE),
+            (topology.is_some(), HAS_TOPOLOGY),
+            (self.edges == Edges::Wrapping, WRAPPING),
+        ] {
+            if enabled {
+                flags |= flag;
+            }
+        }
+
+        let mut bytes = vec![ENCODING_VERSION, flags];
+        write_varint(&mut bytes, self.get_width());
+        write_varint(&mut bytes, self.get_height());
+        bytes.extend([self.mines_per_cell, bits]);
+        if let Some(topology) = topology {
+            write_varint(&mut bytes, topology.len());
+            bytes.extend(topology.as_bytes());
+        }
+
+        let mut writer = BitWriter::new(bytes);
+        if let Some(mask) = &self.mask {
+            mask.data.iter().flatten().for_each(|contained| writer.write(*contained as u8, 1));
+        }
+        cells.iter().for_each(|cell| writer.write(cell.mines, bits));
+        if has_state {
+            cells.iter().for_each(|cell| writer.write(cell.cleared as u8, 1));
+            cells.iter().for_each(|cell| writer.write((cell.mark == Mark::Question) as u8, 1));
+            cells.iter().for_each(|cell| writer.write(cell.flags(), bits));
+        }
+        Some(writer.finish())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Board> {
+        let [version, flags, rest @ ..] = bytes else {
+            return None;
+        };
+        if *version != ENCODING_VERSION {
+            return None;
+        }
+        let mut position = 0;
+        let width = read_varint(rest, &mut position)?;
+        let height = read_varint(rest, &mut position)?;
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let [mines_per_cell, bits] = *rest.get(position..position + 2)? else {
+            return None;
+        };
+        position += 2;
+        if bits == 0 || bits > 8 || bit_width(mines_per_cell) > bits {
+            return None;
+        }
+        let topology = if flags & HAS_TOPOLOGY != 0 {
+            let length = read_varint(rest, &mut position)?;
+            let text = std::str::from_utf8(rest.get(position..position.checked_add(length)?)?).ok()?;
+            position += length;
+            Some(topology_from_text(text)?)
+        } else {
+            None
+        };
+
+        // Checked before allocating, so corrupt sizes can not exhaust memory.
+        let count = width.checked_mul(height)?;
+        let mut cell_bits = count.checked_mul(bits as usize)?;
+        if flags & HAS_MASK != 0 {
+            cell_bits = cell_bits.checked_add(count)?;
+        }
+        if rest.len().saturating_sub(position).checked_mul(8)? < cell_bits {
+            return None;
+        }
+
+        let mut reader = BitReader::new(&rest[position..]);
+        let mut board = Board::new_empty(Size { width, height }).with_mines_per_cell(mines_per_cell);
+        if flags & HAS_MASK != 0 {
+            let mut mask = Vec2 {
+                data: vec![vec![false; height]; width],
+            };
+            mask.for_each_element_mut(|_, contained, _| *contained = reader.read(1) == Some(1));
+            board = board.with_mask(mask);
+        }
+        if let Some(topology) = topology {
+            board.topology = topology;
+        }
+        if flags & WRAPPING != 0 {
+            board.edges = Edges::Wrapping;
+        }
+        if !board.topology.supports(board.get_size(), board.edges) {
+            return None;
+        }
+
+        // Cells above the limit could overflow the numbers around them.
+        if mines_per_cell > board.max_mines_per_cell() {
+            return None;
+        }
+        for_each_cell(&mut board, |cell| {
+            cell.mines = reader.read(bits)?;
+            (cell.mines <= mines_per_cell).then_some(())
+        })?;
+        board.add_cell_numbers();
+
+        if flags & HAS_STATE != 0 {
+            for_each_cell(&mut board, |cell| {
+                cell.cleared = reader.read(1)? == 1;
+                Some(())
+            })?;
+            for_each_cell(&mut board, |cell| {
+                if reader.read(1)? == 1 {
+                    cell.mark = Mark::Question;
+                }
+                Some(())
+            })?;
+            for_each_cell(&mut board, |cell| {
+                let flags = reader.read(bits)?;
+                if flags > 0 {
+                    cell.mark = Mark::Flag(flags);
+                }
+                Some(())
+            })?;
+        }
+        board.can_encode().then_some(board)
+    }
+
+    /// Whether `from_bytes` reads the board back: not empty, with a topology
+    /// that supports its size, at most `mines_per_cell` mines in a cell, and
+    /// the cells outside of the mask empty, hidden and unmarked.
+    fn can_encode(&self) -> bool {
+        let size = self.get_size();
+        if size.width == 0 || size.height == 0 || self.mines_per_cell > self.max_mines_per_cell() || !self.topology.supports(size, self.edges) {
+            return false;
+        }
+        let mut valid = true;
+        self.cells.for_each_element(|point, cell, stop| {
+            let used = cell.is_mine() || cell.cleared || cell.mark != Mark::None;
+            if cell.mines > self.mines_per_cell || (used && !self.contains(point)) {
+                valid = false;
+                *stop = true;
+            }
+        });
+        valid
+    }
+
+    /// `to_bytes` as unpadded URL-safe base64, to share boards as links.
+    pub fn to_code(&self) -> Option<String> {
+        let bytes = self.to_bytes()?;
+        let mut code = String::new();
+        for chunk in bytes.chunks(3) {
+            let value = chunk.iter().enumerate().fold(0u32, |value, (index, byte)| value | (*byte as u32) << (16 - 8 * index));
+            for index in 0..=chunk.len() {
+                code.push(ALPHABET[(value >> (18 - 6 * index) & 63) as usize] as char);
+            }
+        }
+        Some(code)
+    }
+
+    pub fn from_code(code: &str) -> Option<Board> {
+        let mut bytes = vec![];
+        let mut value = 0u32;
+        let mut bits = 0;
+        for char in code.bytes() {
+            let digit = ALPHABET.iter().position(|letter| *letter == char)?;
+            value = value << 6 | digit as u32;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((value >> bits) as u8);
+                value &= (1 << bits) - 1;
+            }
+        }
+        Board::from_bytes(&bytes)
+    }
+}
+
+/// Visits every cell in row order, masked ones included, stopping at the
+/// first `None`.
+fn for_each_cell(board: &mut Board, f: impl FnMut(&mut crate::cell::Cell) -> Option<()>) -> Option<()> {
+    board.cells.data.iter_mut().flatten().try_for_each(f)
+}
+
+/// Bits needed to write every value up to `value`, at least one.
+fn bit_width(value: u8) -> u8 {
+    (8 - value.leading_zeros() as u8).max(1)
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8], position: &mut usize) -> Option<usize> {
+    let mut value: usize = 0;
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = *bytes.get(*position)?;
+        *position += 1;
+        value |= ((byte & 0x7f) as usize).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    used: u8,
+}
+
+impl BitWriter {
+    fn new(bytes: Vec<u8>) -> Self {
+        BitWriter { bytes, used: 8 }
+    }
+
+    fn write(&mut self, value: u8, bits: u8) {
+        for bit in (0..bits).rev() {
+            if self.used == 8 {
+                self.bytes.push(0);
+                self.used = 0;
+            }
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= (value >> bit & 1) << (7 - self.used);
+            self.used += 1;
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        BitReader { bytes, position: 0 }
+    }
+
+    fn read(&mut self, bits: u8) -> Option<u8> {
+        let mut value = 0;
+        for _ in 0..bits {
+            let byte = self.bytes.get(self.position / 8)?;
+            value = value << 1 | (byte >> (7 - self.position % 8) & 1);
+            self.position += 1;
+        }
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(target_arch = "wasm32"))]
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::topology::{Hexagonal, Neighbourhood};
+
+    fn assert_same_board(decoded: &Board, board: &Board) {
+        assert_eq!(decoded.cells, board.cells);
+        assert_eq!(decoded.mask, board.mask);
+        assert_eq!(decoded.edges, board.edges);
+        assert_eq!(decoded.mines_per_cell, board.mines_per_cell);
+        assert_eq!(
+            decoded.topology.to_text().unwrap_or_default(),
+            board.topology.to_text().unwrap_or_default()
+        );
+    }
+
+    #[test]
+    fn test_hard_board_size() {
+        let board = Board::new(99, Size { width: 30, height: 16 });
+
+        assert_eq!(board.to_bytes().unwrap().len(), 2 + 2 + 2 + 60);
+        assert_eq!(board.to_code().unwrap().len(), 88);
+        assert_same_board(&Board::from_code(&board.to_code().unwrap()).unwrap(), &board);
+    }
+
+    #[test]
+    fn test_known_code() {
+        let board = Board::from_text("*..\n...\n..*").unwrap();
+
+        // Boards read from text always have a mask.
+        assert_eq!(board.to_bytes().unwrap(), vec![1, HAS_MASK, 3, 3, 1, 1, 0b1111_1111, 0b1100_0000, 0b0100_0000]);
+        assert_eq!(board.to_code().unwrap(), "AQEDAwEB_8BA");
+        assert_same_board(&Board::from_code("AQEDAwEB_8BA").unwrap(), &board);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(Board::from_bytes(&[]).is_none());
+        assert!(Board::from_bytes(&[2, 0, 1, 1, 1, 1, 0]).is_none());
+        assert!(Board::from_bytes(&[1, 0, 0xff, 0xff, 0xff, 0xff, 0x0f, 0xff, 0xff, 0x0f, 1, 1]).is_none());
+        assert!(Board::from_code("not base64!").is_none());
+        assert!(Board::from_bytes(&[1, 0, 3, 3, 1, 8, 100, 100, 100, 100, 0, 100, 100, 100, 100]).is_none());
+        assert!(Board::from_bytes(&[1, 0, 1, 1, 2, 1, 0]).is_none());
+        assert!(Board::from_bytes(&[1, 0, 0xff, 0xff, 0xff, 0xff, 0x0f, 0, 1, 1]).is_none());
+        // The masked out cell is a revealed mine with a flag.
+        assert!(Board::from_bytes(&[1, 1, 1, 2, 1, 1, 0b1011_0000]).is_none());
+
+        let overflow = Board::from_text("*.\n..").unwrap().with_topology(Neighbourhood::new(vec![(i32::MAX, i32::MAX), (i32::MIN, i32::MIN)]));
+        let decoded = Board::from_code(&overflow.to_code().unwrap()).unwrap();
+        assert_eq!(decoded.cell_at(Point { x: 1, y: 1 }).unwrap().number, 0);
+
+        let hexagonal = Board::new_empty(Size { width: 3, height: 3 }).with_topology(Hexagonal).with_edges(Edges::Wrapping);
+        assert!(hexagonal.to_bytes().is_none());
+        let mut bytes = hexagonal.with_edges(Edges::Bounded).to_bytes().unwrap();
+        bytes[1] |= WRAPPING;
+        assert!(Board::from_bytes(&bytes).is_none());
+
+        let mut masked = Board::from_text("*.#\n...").unwrap();
+        assert!(masked.to_bytes().is_some());
+        masked.cells.data[0][2].mark = Mark::Question;
+        assert!(masked.to_bytes().is_none());
+        let bytes = Board::from_text("*..\n...").unwrap().to_bytes().unwrap();
+        assert!(Board::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn board_strategy() -> impl Strategy<Value = Board> {
+        (1usize..12, 1usize..12, 1u8..4, any::<u64>(), 0usize..3, any::<bool>(), any::<bool>()).prop_map(
+            |(width, height, mines_per_cell, seed, topology, wrapping, masked)| {
+                use rand::{Rng, SeedableRng};
+                let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+
+                let mut board = Board::new_empty(Size { width, height }).with_mines_per_cell(mines_per_cell);
+                if masked {
+                    let mut mask = Vec2 {
+                        data: vec![vec![true; height]; width],
+                    };
+                    mask.for_each_element_mut(|_, contained, _| *contained = rng.gen_bool(0.8));
+                    board = board.with_mask(mask);
+                }
+                board = match topology {
+                    0 => board,
+                    1 => board.with_topology(Hexagonal),
+                    _ => board.with_topology(Neighbourhood::knight()),
+                };
+                if wrapping && board.topology.supports(board.get_size(), Edges::Wrapping) {
+                    board = board.with_edges(Edges::Wrapping);
+                }
+
+                let contained = board.get_size().width * board.get_size().height / 3;
+                _ = board.add_mines_with_rng(rng.gen_range(0..=contained as i32) / 2, &mut rng);
+                board.add_cell_numbers();
+                board.for_each_cell_mut(|_, cell, _| match rng.gen_range(0..4) {
+                    0 => cell.cleared = true,
+                    1 => cell.mark = Mark::Question,
+                    2 => cell.mark = Mark::Flag(rng.gen_range(1..=mines_per_cell)),
+                    _ => {}
+                });
+                board
+            },
+        )
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    proptest! {
+        #[test]
+        fn test_bytes_round_trip(board in board_strategy()) {
+            assert_same_board(&Board::from_bytes(&board.to_bytes().unwrap()).unwrap(), &board);
+        }
+
+        #[test]
+        fn test_code_round_trip(board in board_strategy()) {
+            let code = board.to_code().unwrap();
+            prop_assert!(code.bytes().all(|char| char.is_ascii_alphanumeric() || char == b'-' || char == b'_'));
+            assert_same_board(&Board::from_code(&code).unwrap(), &board);
+        }
+
+        #[test]
+        fn test_arbitrary_cells_do_not_panic(
+            width in 1u8..8,
+            height in 1u8..8,
+            mines_per_cell in any::<u8>(),
+            bits in 1u8..=8,
+            cells in proptest::collection::vec(any::<u8>(), 0..64),
+        ) {
+            let mut bytes = vec![ENCODING_VERSION, 0, width, height, mines_per_cell, bits];
+            bytes.extend(cells);
+            let _ = Board::from_bytes(&bytes);
+        }
+
+        #[test]
+        fn test_arbitrary_bytes_do_not_panic(bytes in proptest::collection::vec(any::<u8>(), 0..64)) {
+            let _ = Board::from_bytes(&bytes);
+        }
+    }
+}
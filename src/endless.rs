@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+
+use rand::RngCore;
+
+use crate::cell::{Cell, Mark};
+use crate::graphics::*;
+use crate::rng::{splitmix64, SeededRng};
+
+pub const CHUNK_SIZE: usize = 16;
+
+/// Reveals stop after this many cells, as sparse boards can have
+/// unbounded empty areas. The cells at the edge of the cleared area stay
+/// hidden, so revealing one of them carries on.
+const MAX_CASCADE: usize = 4096;
+
+/// Infinite board split in square chunks, generated the first time they are
+/// touched. The mines of a chunk only depend on the seed and the chunk
+/// coordinates, so the same seed always gives the same board.
+#[derive(Debug, Clone)]
+pub struct EndlessBoard {
+    pub seed: u64,
+    pub mines_per_chunk: usize,
+    chunks: HashMap<SignedPoint, Vec2<Cell>>,
+}
+
+impl EndlessBoard {
+    pub fn new(seed: u64, mines_per_chunk: usize) -> Self {
+        EndlessBoard {
+            seed,
+            mines_per_chunk: mines_per_chunk.min(CHUNK_SIZE * CHUNK_SIZE),
+            chunks: HashMap::new(),
+        }
+    }
+
+    pub fn cell_at(&mut self, coordinates: SignedPoint) -> &Cell {
+        let (chunk, local) = EndlessBoard::split(coordinates);
+        self.generate_chunk(chunk);
+        self.chunks[&chunk].get_element(local).unwrap()
+    }
+
+    /// Like `cell_at`, without generating the chunk.
+    pub fn generated_cell_at(&self, coordinates: SignedPoint) -> Option<&Cell> {
+        let (chunk, local) = EndlessBoard::split(coordinates);
+        self.chunks.get(&chunk)?.get_element(local)
+    }
+
+    pub fn replace_cell(&mut self, new_cell: Cell, coordinates: SignedPoint) {
+        let (chunk, local) = EndlessBoard::split(coordinates);
+        self.generate_chunk(chunk);
+        if let Some(cells) = self.chunks.get_mut(&chunk) {
+            cells.replace_at(new_cell, local);
+        }
+    }
+
+    pub fn get_cells_around(&mut self, coordinates: SignedPoint) -> Vec<(SignedPoint, Cell)> {
+        EndlessBoard::points_around(coordinates).into_iter().map(|point| (point, *self.cell_at(point))).collect()
+    }
+
+    pub fn chunks_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    fn points_around(coordinates: SignedPoint) -> Vec<SignedPoint> {
+        let mut points = vec![];
+        for dx in [-1, 0, 1] {
+            for dy in [-1, 0, 1] {
+                if dx != 0 || dy != 0 {
+                    points.push(SignedPoint {
+                        x: coordinates.x + dx,
+                        y: coordinates.y + dy,
+                    });
+                }
+            }
+        }
+        points
+    }
+
+    /// Chunk coordinates and position inside of it.
+    fn split(coordinates: SignedPoint) -> (SignedPoint, Point) {
+        let size = CHUNK_SIZE as i64;
+        let chunk = SignedPoint {
+            x: coordinates.x.div_euclid(size),
+            y: coordinates.y.div_euclid(size),
+        };
+        let local = Point {
+            x: coordinates.x.rem_euclid(size) as usize,
+            y: coordinates.y.rem_euclid(size) as usize,
+        };
+        (chunk, local)
+    }
+
+    fn generate_chunk(&mut self, chunk: SignedPoint) {
+        if self.chunks.contains_key(&chunk) {
+            return;
+        }
+
+        // Numbers on the borders need the mines of the chunks around,
+        // which are cheap to compute again from the seed.
+        let mut mines: HashMap<SignedPoint, Vec2<bool>> = HashMap::new();
+        for dx in [-1, 0, 1] {
+            for dy in [-1, 0, 1] {
+                let neighbour = SignedPoint {
+                    x: chunk.x + dx,
+                    y: chunk.y + dy,
+                };
+                mines.insert(neighbour, self.chunk_mines(neighbour));
+            }
+        }
+        let is_mine = |point: SignedPoint| {
+            let (chunk, local) = EndlessBoard::split(point);
+            mines[&chunk].get_element(local) == Some(&true)
+        };
+
+        let origin = SignedPoint {
+            x: chunk.x * CHUNK_SIZE as i64,
+            y: chunk.y * CHUNK_SIZE as i64,
+        };
+        let mut data = vec![];
+        for x in 0..CHUNK_SIZE {
+            let mut row = vec![];
+            for y in 0..CHUNK_SIZE {
+                let point = SignedPoint {
+                    x: origin.x + x as i64,
+                    y: origin.y + y as i64,
+                };
+                let coordinates = Point { x, y };
+                let cell = if is_mine(point) {
+                    Cell::new_mine(coordinates)
+                } else {
+                    Cell {
+                        number: EndlessBoard::points_around(point).into_iter().filter(|point| is_mine(*point)).count() as i8,
+                        coordinates,
+                        ..Default::default()
+                    }
+                };
+                row.push(cell);
+            }
+            data.push(row);
+        }
+        self.chunks.insert(chunk, Vec2 { data });
+    }
+
+    fn chunk_mines(&self, chunk: SignedPoint) -> Vec2<bool> {
+        // Stored seeds must keep giving the same world, see `SeededRng`.
+        let mut rng = SeededRng::new(chunk_seed(self.seed, chunk));
+        let mut mines = Vec2 {
+            data: vec![vec![false; CHUNK_SIZE]; CHUNK_SIZE],
+        };
+        let mut placed = 0;
+        while placed < self.mines_per_chunk {
+            let index = (rng.next_u64() % (CHUNK_SIZE * CHUNK_SIZE) as u64) as usize;
+            let point = Point {
+                x: index / CHUNK_SIZE,
+                y: index % CHUNK_SIZE,
+            };
+            if mines.get_element(point) == Some(&false) {
+                mines.replace_at(true, point);
+                placed += 1;
+            }
+        }
+        mines
+    }
+}
+
+fn chunk_seed(seed: u64, chunk: SignedPoint) -> u64 {
+    [chunk.x as u64, chunk.y as u64].iter().fold(splitmix64(seed), |hash, value| splitmix64(hash ^ value))
+}
+
+/// Endless mode: reveal as many safe cells as possible before hitting a mine.
+#[derive(Debug, Clone)]
+pub struct EndlessGame {
+    pub board: EndlessBoard,
+    score: u64,
+    game_over: bool,
+}
+
+impl EndlessGame {
+    pub fn new(seed: u64, mines_per_chunk: usize) -> Self {
+        EndlessGame {
+            board: EndlessBoard::new(seed, mines_per_chunk),
+            score: 0,
+            game_over: false,
+        }
+    }
+
+    /// Safe cells revealed so far.
+    pub fn score(&self) -> u64 {
+        self.score
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    pub fn selected_at(&mut self, coordinates: SignedPoint) -> Cell {
+        let cell = *self.board.cell_at(coordinates);
+        if self.game_over || cell.cleared || cell.is_flagged() {
+            return cell;
+        }
+
+        let mut stack = vec![coordinates];
+        let mut cleared = 0;
+        while let Some(point) = stack.pop() {
+            let cell = *self.board.cell_at(point);
+            if cell.cleared || cell.is_flagged() || cleared >= MAX_CASCADE {
+                continue;
+            }
+            self.board.replace_cell(Cell { cleared: true, ..cell }, point);
+            cleared += 1;
+
+            if cell.is_mine() {
+                self.game_over = true;
+                continue;
+            }
+            self.score += 1;
+            if cell.number == 0 {
+                EndlessBoard::points_around(point).into_iter().for_each(|point| stack.push(point));
+            }
+        }
+        cell
+    }
+
+    pub fn toggle_flagged(&mut self, coordinates: SignedPoint) -> bool {
+        let cell = *self.board.cell_at(coordinates);
+        if self.game_over || cell.cleared {
+            return false;
+        }
+
+        let mark = if cell.is_flagged() { Mark::None } else { Mark::Flag(1) };
+        self.board.replace_cell(Cell { mark, ..cell }, coordinates);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_are_deterministic() {
+        let points = [
+            SignedPoint { x: 0, y: 0 },
+            SignedPoint { x: -1, y: 15 },
+            SignedPoint { x: 300, y: -42 },
+            SignedPoint { x: -1000, y: -1000 },
+        ];
+        let mut board = EndlessBoard::new(42, 40);
+        let mut other = EndlessBoard::new(42, 40);
+
+        // Generate the chunks in a different order.
+        points.iter().for_each(|point| _ = board.cell_at(*point));
+        points.iter().rev().for_each(|point| _ = other.cell_at(*point));
+
+        points.iter().for_each(|point| {
+            let (cell, other) = (*board.cell_at(*point), *other.cell_at(*point));
+            assert_eq!(cell.mines, other.mines);
+            assert_eq!(cell.number, other.number);
+        });
+        assert_eq!(board.chunks_count(), 4);
+    }
+
+    #[test]
+    fn test_chunk_layout_is_stable() {
+        // Changing this layout breaks every stored seed.
+        let mut board = EndlessBoard::new(42, 4);
+        let mut mines = vec![];
+        for x in 0..16 {
+            for y in 0..16 {
+                if board.cell_at(SignedPoint { x, y }).is_mine() {
+                    mines.push((x, y));
+                }
+            }
+        }
+        assert_eq!(mines, [(0, 10), (0, 12), (7, 11), (9, 3)]);
+    }
+
+    #[test]
+    fn test_mines_per_chunk() {
+        let mut board = EndlessBoard::new(7, 40);
+        let mut mines = 0;
+        for x in -16..0 {
+            for y in 16..32 {
+                if board.cell_at(SignedPoint { x, y }).is_mine() {
+                    mines += 1;
+                }
+            }
+        }
+        assert_eq!(mines, 40);
+        assert_eq!(board.chunks_count(), 1);
+    }
+
+    #[test]
+    fn test_numbers_across_chunk_borders() {
+        let mut board = EndlessBoard::new(3, 60);
+        for x in -18..18 {
+            for y in [-1, 0, 15, 16] {
+                let point = SignedPoint { x, y };
+                let cell = *board.cell_at(point);
+                if cell.is_mine() {
+                    continue;
+                }
+                let mines = board.get_cells_around(point).iter().filter(|(_, cell)| cell.is_mine()).count();
+                assert_eq!(cell.number as usize, mines);
+            }
+        }
+    }
+
+    #[test]
+    fn test_endless_game() {
+        let mut game = EndlessGame::new(11, 40);
+        let mut safe = None;
+        let mut mine = None;
+        for x in 0..CHUNK_SIZE as i64 {
+            for y in 0..CHUNK_SIZE as i64 {
+                let point = SignedPoint { x, y };
+                let cell = *game.board.cell_at(point);
+                if cell.is_mine() {
+                    mine = Some(point);
+                } else if cell.number > 0 {
+                    safe = Some(point);
+                }
+            }
+        }
+
+        game.selected_at(safe.unwrap());
+        assert_eq!(game.score(), 1);
+        game.selected_at(safe.unwrap());
+        assert_eq!(game.score(), 1);
+
+        assert!(game.toggle_flagged(mine.unwrap()));
+        game.selected_at(mine.unwrap());
+        assert!(!game.is_game_over());
+
+        game.toggle_flagged(mine.unwrap());
+        game.selected_at(mine.unwrap());
+        assert!(game.is_game_over());
+        assert_eq!(game.score(), 1);
+        assert!(!game.toggle_flagged(SignedPoint { x: -5, y: -5 }));
+    }
+}
@@ -0,0 +1,320 @@
+//! C ABI, built with `--features ffi`. The header is generated by cbindgen
+//! into `include/minesweeper_core.h`.
+//!
+//! Games are opaque handles created by `ms_game_new`, `ms_game_new_custom`
+//! or `ms_game_from_text` and released with `ms_game_free`. Coordinates
+//! follow `Point`: `x` is the row and `y` the column.
+
+use std::ffi::{c_char, CStr};
+use std::ptr;
+
+use crate::board::Board;
+use crate::game::{Difficulty, Game};
+use crate::graphics::{Point, Size};
+use crate::rng::SeededRng;
+use crate::view::VisibleCell;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    NullPointer,
+    OutOfBounds,
+    /// The action did not change anything, like flagging a revealed cell.
+    Rejected,
+    /// The game was already won or lost.
+    GameOver,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GameState {
+    Playing,
+    Won,
+    Lost,
+    /// Returned for null handles.
+    Invalid,
+}
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CellState {
+    Hidden,
+    Flagged,
+    Questioned,
+    Revealed,
+    Mine,
+    Void,
+}
+
+/// What the player sees of a cell. `number` is the count of neighbouring
+/// mines for revealed cells and the number of flags for flagged ones.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CellInfo {
+    pub state: CellState,
+    pub number: i8,
+}
+
+impl From<VisibleCell> for CellInfo {
+    fn from(cell: VisibleCell) -> Self {
+        let (state, number) = match cell {
+            VisibleCell::Hidden => (CellState::Hidden, 0),
+            VisibleCell::Flagged(flags) => (CellState::Flagged, flags as i8),
+            VisibleCell::Questioned => (CellState::Questioned, 0),
+            VisibleCell::Revealed(number) => (CellState::Revealed, number),
+            VisibleCell::Mine => (CellState::Mine, 0),
+            VisibleCell::Void => (CellState::Void, 0),
+        };
+        CellInfo { state, number }
+    }
+}
+
+/// Opaque game handle.
+pub struct GameHandle {
+    game: Game,
+}
+
+/// Largest board `ms_game_new_custom` creates, so that huge sizes fail
+/// instead of aborting on allocation.
+const MAX_CELLS: usize = 1 << 20;
+
+fn into_handle(game: Game) -> *mut GameHandle {
+    Box::into_raw(Box::new(GameHandle { game }))
+}
+
+/// Game with one of the `MsDifficulty` values. Returns null for any other
+/// value.
+#[no_mangle]
+pub extern "C" fn ms_game_new(difficulty: u32) -> *mut GameHandle {
+    let difficulties = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+    match difficulties.into_iter().find(|known| known.clone() as u32 == difficulty) {
+        Some(difficulty) => into_handle(Game::new(difficulty)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Game with `mines` placed from `seed`. Returns null when the board is
+/// empty, has more than 2^20 cells or the mines do not fit.
+#[no_mangle]
+pub extern "C" fn ms_game_new_custom(width: usize, height: usize, mines: u32, seed: u64) -> *mut GameHandle {
+    let Some(cells) = width.checked_mul(height) else {
+        return ptr::null_mut();
+    };
+    if cells == 0 || cells > MAX_CELLS || mines as usize >= cells {
+        return ptr::null_mut();
+    }
+    let board = Board::new_with_rng(mines as i32, Size { width, height }, &mut SeededRng::new(seed));
+    into_handle(Game::new_with_board(board, Difficulty::Easy))
+}
+
+/// Game on a fixed layout in the `Board::from_text` format. Returns null
+/// when the text is not a valid board.
+///
+/// # Safety
+///
+/// `text` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_from_text(text: *const c_char) -> *mut GameHandle {
+    if text.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(text) = CStr::from_ptr(text).to_str() else {
+        return ptr::null_mut();
+    };
+    match Board::from_text(text) {
+        Some(board) => into_handle(Game::new_with_board(board, Difficulty::Easy)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// # Safety
+///
+/// `game` must be null or a handle that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_free(game: *mut GameHandle) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// # Safety
+///
+/// `game` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_width(game: *const GameHandle) -> usize {
+    game.as_ref().map_or(0, |handle| handle.game.board.get_width())
+}
+
+/// # Safety
+///
+/// `game` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_height(game: *const GameHandle) -> usize {
+    game.as_ref().map_or(0, |handle| handle.game.board.get_height())
+}
+
+/// # Safety
+///
+/// `game` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_state(game: *const GameHandle) -> GameState {
+    let Some(handle) = game.as_ref() else {
+        return GameState::Invalid;
+    };
+    state(&handle.game)
+}
+
+/// # Safety
+///
+/// `game` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_flags_remaining(game: *const GameHandle) -> i32 {
+    game.as_ref().map_or(0, |handle| handle.game.flags_remaining())
+}
+
+/// Rejected for cells already revealed or flagged.
+///
+/// # Safety
+///
+/// `game` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_reveal(game: *mut GameHandle, x: usize, y: usize) -> Status {
+    play(game, Point { x, y }, |game, coordinates| {
+        if game.board.cell_at(coordinates).is_some_and(|cell| cell.cleared || cell.is_flagged()) {
+            return false;
+        }
+        game.selected_at(coordinates);
+        true
+    })
+}
+
+/// Toggles a flag on a hidden cell.
+///
+/// # Safety
+///
+/// `game` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_flag(game: *mut GameHandle, x: usize, y: usize) -> Status {
+    play(game, Point { x, y }, Game::toggle_flagged)
+}
+
+/// # Safety
+///
+/// `game` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_chord(game: *mut GameHandle, x: usize, y: usize) -> Status {
+    play(game, Point { x, y }, |game, coordinates| !game.chord_at(coordinates).is_empty())
+}
+
+/// Writes what the player sees at the coordinates into `cell`.
+///
+/// # Safety
+///
+/// `game` must be null or a live handle, and `cell` null or writable.
+#[no_mangle]
+pub unsafe extern "C" fn ms_game_cell(game: *const GameHandle, x: usize, y: usize, cell: *mut CellInfo) -> Status {
+    let (Some(handle), Some(cell)) = (game.as_ref(), cell.as_mut()) else {
+        return Status::NullPointer;
+    };
+    match handle.game.player_view().cell_at(Point { x, y }) {
+        Some(visible) => {
+            *cell = CellInfo::from(visible);
+            Status::Ok
+        }
+        None => Status::OutOfBounds,
+    }
+}
+
+fn state(game: &Game) -> GameState {
+    if game.is_game_over() {
+        GameState::Lost
+    } else if game.is_win() {
+        GameState::Won
+    } else {
+        GameState::Playing
+    }
+}
+
+unsafe fn play(game: *mut GameHandle, coordinates: Point, action: impl FnOnce(&mut Game, Point) -> bool) -> Status {
+    let Some(handle) = game.as_mut() else {
+        return Status::NullPointer;
+    };
+    if !handle.game.board.contains(coordinates) {
+        return Status::OutOfBounds;
+    }
+    if state(&handle.game) != GameState::Playing {
+        return Status::GameOver;
+    }
+    if action(&mut handle.game, coordinates) {
+        Status::Ok
+    } else {
+        Status::Rejected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_handle() {
+        unsafe {
+            let game = ms_game_from_text(c"*..\n...\n...".as_ptr());
+            assert!(!game.is_null());
+            assert_eq!((ms_game_width(game), ms_game_height(game)), (3, 3));
+
+            assert_eq!(ms_game_reveal(game, 3, 0), Status::OutOfBounds);
+            assert_eq!(ms_game_reveal(game, 1, 1), Status::Ok);
+            assert_eq!(ms_game_reveal(game, 1, 1), Status::Rejected);
+            assert_eq!(ms_game_chord(game, 1, 1), Status::Rejected);
+            assert_eq!(ms_game_flag(game, 2, 2), Status::Ok);
+            assert_eq!(ms_game_reveal(game, 2, 2), Status::Rejected);
+            assert_eq!(ms_game_flag(game, 2, 2), Status::Ok);
+            assert_eq!(ms_game_flag(game, 0, 0), Status::Ok);
+            assert_eq!(ms_game_state(game), GameState::Won);
+            assert_eq!(ms_game_chord(game, 1, 1), Status::GameOver);
+
+            let mut cell = CellInfo {
+                state: CellState::Void,
+                number: 0,
+            };
+            assert_eq!(ms_game_cell(game, 1, 1, &mut cell), Status::Ok);
+            assert_eq!(
+                cell,
+                CellInfo {
+                    state: CellState::Revealed,
+                    number: 1
+                }
+            );
+            assert_eq!(ms_game_cell(game, 0, 0, ptr::null_mut()), Status::NullPointer);
+
+            ms_game_free(game);
+        }
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        unsafe {
+            assert!(ms_game_from_text(c"*.\n...".as_ptr()).is_null());
+            assert!(ms_game_new_custom(2, 2, 4, 0).is_null());
+            assert_eq!(ms_game_reveal(ptr::null_mut(), 0, 0), Status::NullPointer);
+            assert!(ms_game_new_custom(usize::MAX, 2, 1, 0).is_null());
+            assert!(ms_game_new_custom(1 << 16, 1 << 16, 1, 0).is_null());
+            assert!(ms_game_new(3).is_null());
+            assert_eq!(ms_game_state(ptr::null()), GameState::Invalid);
+
+            let game = ms_game_new_custom(4, 5, 3, 9);
+            assert_eq!(ms_game_flags_remaining(game), 3);
+            ms_game_free(game);
+
+            let game = ms_game_new_custom(100, 100, 9999, 1);
+            assert_eq!(ms_game_flags_remaining(game), 9999);
+            ms_game_free(game);
+
+            let game = ms_game_new(Difficulty::Medium as u32);
+            assert_eq!(ms_game_flags_remaining(game), 41);
+            ms_game_free(game);
+        }
+    }
+}
@@ -1,8 +1,17 @@
 use core::fmt;
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::Rng;
 
 use crate::board::Board;
-use crate::cell::Cell;
+use crate::cell::{Cell, Mark};
+use crate::clock::{Clock, SystemClock};
 use crate::graphics::*;
+use crate::rules::{FlagLimit, Loss, Rules};
+use crate::stats::{Efficiency, Statistics};
+use crate::view::PlayerView;
 
 struct GameConfiguration {
     mines_count: i32,
@@ -40,7 +49,10 @@ impl GameConfiguration {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "wasm", wasm_bindgen::prelude::wasm_bindgen)]
+#[repr(C)]
 pub enum Difficulty {
     Easy,
     Medium,
@@ -55,59 +67,261 @@ impl fmt::Display for Difficulty {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    MineHit { coordinates: Point, lives_remaining: u32 },
+}
+
 #[derive(Debug, Clone)]
 pub struct Game {
     pub board: Board,
     pub total_mines: i32,
     pub difficulty: Difficulty,
+    pub statistics: Statistics,
+    pub rules: Rules,
+    events: Vec<GameEvent>,
+    clock: Arc<dyn Clock>,
+    started_at: Option<Duration>,
+    ended_at: Option<Duration>,
+    /// Time played before the game was loaded.
+    pub(crate) resumed_elapsed: Duration,
 }
 
 impl Game {
     pub fn new(difficulty: Difficulty) -> Game {
         let config = GameConfiguration::configuration_for(&difficulty);
+        Game::new_with_board(Board::new(config.mines_count, config.size), difficulty)
+    }
+
+    /// Game with mines placed by `rng`, for seeded games or platforms
+    /// without a thread RNG.
+    pub fn new_with_rng(difficulty: Difficulty, rng: &mut impl Rng) -> Game {
+        let config = GameConfiguration::configuration_for(&difficulty);
+        Game::new_with_board(Board::new_with_rng(config.mines_count, config.size, rng), difficulty)
+    }
+
+    pub fn new_with_board(board: Board, difficulty: Difficulty) -> Game {
+        let mut total_mines = 0;
+        board.for_each_cell(|_, cell, _| {
+            total_mines += cell.mines as i32;
+        });
         Game {
-            board: Board::new(config.mines_count, config.size),
-            total_mines: config.mines_count,
+            board,
+            total_mines,
             difficulty,
+            statistics: Statistics::default(),
+            rules: Rules::default(),
+            events: vec![],
+            clock: Arc::new(SystemClock::default()),
+            started_at: None,
+            ended_at: None,
+            resumed_elapsed: Duration::ZERO,
         }
     }
 
+    pub fn with_rules(self, rules: Rules) -> Game {
+        Game { rules, ..self }
+    }
+
+    pub fn with_clock(self, clock: Arc<dyn Clock>) -> Game {
+        Game { clock, ..self }
+    }
+
     pub fn get_board(&self) -> &Board {
         &self.board
     }
 
-    pub fn remaining_mines(&self) -> u32 {
+    pub fn player_view(&self) -> PlayerView {
+        self.board.player_view()
+    }
+
+    /// Mine counter shown to the player: total mines (or the flag limit)
+    /// minus flags placed, regardless of whether the flags are right.
+    /// Can go negative when flags are unlimited.
+    pub fn flags_remaining(&self) -> i32 {
+        let mut flags: i32 = 0;
+        self.board.for_each_cell(|_, cell, _| {
+            flags += cell.flags() as i32;
+        });
+
+        self.flag_limit().unwrap_or(self.total_mines) - flags
+    }
+
+    pub fn flag_limit(&self) -> Option<i32> {
+        match self.rules.flag_limit {
+            FlagLimit::Unlimited => None,
+            FlagLimit::Mines => Some(self.total_mines),
+            FlagLimit::Count(count) => Some(count as i32),
+        }
+    }
+
+    /// Mines that are neither flagged nor cleared. Only meant for internal
+    /// checks, as it reveals whether the flags are placed on real mines.
+    pub(crate) fn remaining_mines(&self) -> u32 {
         let mut mines_count: u32 = 0;
         self.board.for_each_cell(|_, cell, _| {
-            if cell.is_mine() && !(cell.cleared || cell.flagged) {
-                mines_count += 1;
+            if !(cell.cleared || cell.flags() == cell.mines) {
+                mines_count += cell.mines as u32;
             }
         });
 
         mines_count
     }
 
-    pub fn toggle_flagged(&mut self, coordinates: Point) {
+    /// Returns whether the mark changed, which fails on cleared cells
+    /// or when the rules have no flags left.
+    pub fn toggle_flagged(&mut self, coordinates: Point) -> bool {
         let Some(cell) = self.board.cell_at(coordinates) else {
-            return;
+            return false;
         };
 
-        if cell.cleared {
-            return;
+        let mark = if cell.is_flagged() { Mark::None } else { Mark::Flag(1) };
+        self.mark(coordinates, mark)
+    }
+
+    /// Places one more flag on the cell, up to the board's `mines_per_cell`.
+    pub fn add_flag(&mut self, coordinates: Point) -> bool {
+        let Some(cell) = self.board.cell_at(coordinates) else {
+            return false;
+        };
+
+        let flags = (cell.flags() + 1).min(self.board.mines_per_cell);
+        self.mark(coordinates, Mark::Flag(flags))
+    }
+
+    pub fn remove_flag(&mut self, coordinates: Point) -> bool {
+        let Some(cell) = self.board.cell_at(coordinates) else {
+            return false;
+        };
+
+        let mark = match cell.flags() {
+            0 | 1 => Mark::None,
+            flags => Mark::Flag(flags - 1),
+        };
+        self.mark(coordinates, mark)
+    }
+
+    /// Cycles hidden -> flag -> question mark -> hidden, skipping
+    /// the question mark when the rules disable them.
+    pub fn cycle_mark(&mut self, coordinates: Point) -> bool {
+        let Some(cell) = self.board.cell_at(coordinates) else {
+            return false;
+        };
+
+        let mark = match cell.mark {
+            Mark::None => Mark::Flag(1),
+            Mark::Flag(_) if self.rules.question_marks => Mark::Question,
+            Mark::Flag(_) | Mark::Question => Mark::None,
+        };
+        self.mark(coordinates, mark)
+    }
+
+    pub(crate) fn mark(&mut self, coordinates: Point, mark: Mark) -> bool {
+        let Some(cell) = self.board.cell_at(coordinates).copied() else {
+            return false;
+        };
+        if self.is_game_over() {
+            return false;
+        }
+        self.start_clock();
+
+        let new_cell = Cell { mark, ..cell };
+        let added_flags = new_cell.flags() as i32 - cell.flags() as i32;
+        let out_of_flags = self.flag_limit().is_some() && added_flags > self.flags_remaining();
+
+        self.statistics.right_clicks += 1;
+        if cell.cleared || out_of_flags || added_flags <= 0 {
+            self.statistics.wasted_clicks += 1;
+        }
+
+        if cell.cleared || out_of_flags || mark == cell.mark {
+            return false;
         }
 
-        let new_cell = Cell { flagged: !cell.flagged, ..*cell };
         let mut m_cells = self.board.cells.clone();
         m_cells.replace_at(new_cell, cell.coordinates);
         self.board.cells = m_cells;
+        self.stop_clock_if_finished();
+        true
     }
 
     pub fn selected_at(&mut self, coordinates: Point) -> Cell {
+        let Some(cell) = self.board.cell_at(coordinates).copied() else {
+            return Cell::default();
+        };
+        if self.is_game_over() {
+            return Cell::default();
+        }
+        self.start_clock();
+
+        self.statistics.left_clicks += 1;
+        if cell.cleared {
+            self.statistics.wasted_clicks += 1;
+        }
+        if self.is_out_of_moves() {
+            return Cell::default();
+        }
+
+        let selected_cell = self.reveal(coordinates);
+        self.stop_clock_if_finished();
+        selected_cell
+    }
+
+    fn reveal(&mut self, coordinates: Point) -> Cell {
         let mut selected_cell: Cell = Cell::default();
         Game::process_selected(&mut self.board, coordinates, &mut selected_cell);
+
+        if selected_cell.is_mine() && !selected_cell.cleared {
+            let lives_remaining = self.lives_remaining();
+            if lives_remaining > 0 {
+                let flagged = Cell {
+                    cleared: true,
+                    mark: Mark::Flag(selected_cell.mines),
+                    ..selected_cell
+                };
+                self.board.replace_cell(flagged, coordinates);
+            }
+            self.events.push(GameEvent::MineHit { coordinates, lives_remaining });
+        }
         selected_cell
     }
 
+    /// Clears every hidden, non flagged cell around a cleared number
+    /// whose mines are all flagged. Returns the cells that were cleared.
+    pub fn chord_at(&mut self, coordinates: Point) -> Vec<Cell> {
+        let Some(cell) = self.board.cell_at(coordinates).cloned() else {
+            return vec![];
+        };
+        if self.is_game_over() {
+            return vec![];
+        }
+        self.start_clock();
+
+        self.statistics.chords += 1;
+        if self.is_out_of_moves() {
+            return vec![];
+        }
+
+        let around = self.board.get_cells_around(coordinates);
+        let flags: usize = around.iter().map(|cell| cell.flags() as usize).sum();
+        let hidden: Vec<Cell> = around.into_iter().filter(|cell| !cell.cleared && !cell.is_flagged()).cloned().collect();
+
+        if !cell.cleared || cell.is_mine() || cell.number as usize != flags || hidden.is_empty() {
+            self.statistics.wasted_clicks += 1;
+            return vec![];
+        }
+
+        hidden.iter().for_each(|cell| {
+            self.reveal(cell.coordinates);
+        });
+        self.stop_clock_if_finished();
+        hidden
+    }
+
+    pub fn efficiency(&self, elapsed: Duration) -> Efficiency {
+        self.statistics.efficiency(self.board.bbbv(), elapsed)
+    }
+
     fn process_selected(board: &mut Board, coordinates: Point, selected_cell: &mut Cell) {
         if let Some(cell) = board.cell_at(coordinates).cloned() {
             *selected_cell = cell;
@@ -131,7 +345,7 @@ impl Game {
                     }
                     checked_coordinates.push(point);
 
-                    if cell.number == 0 {
+                    if cell.number == 0 && !cell.is_mine() {
                         let next_cells = board.get_cells_around(cell.coordinates);
 
                         next_cells.iter().for_each(|cell| {
@@ -149,29 +363,91 @@ impl Game {
         }
     }
 
-    pub fn is_game_over(&self) -> bool {
-        let mut is_game_over = false;
-        self.board.for_each_cell(|_, cell, stop| {
+    /// Mines hit so far, each one costs a life.
+    pub fn mines_hit(&self) -> u32 {
+        let mut mines_hit = 0;
+        self.board.for_each_cell(|_, cell, _| {
             if cell.is_mine() && cell.cleared {
-                is_game_over = true;
-                *stop = true;
+                mines_hit += 1;
             }
         });
-        is_game_over
+        mines_hit
     }
 
-    pub fn is_win(&self) -> bool {
-        if self.remaining_mines() > 0 {
-            return false;
+    pub fn lives_remaining(&self) -> u32 {
+        self.rules.lives.saturating_sub(self.mines_hit())
+    }
+
+    /// Events produced since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        mem::take(&mut self.events)
+    }
+
+    /// Reveal actions done so far: left clicks and chords.
+    pub fn moves(&self) -> u32 {
+        self.statistics.left_clicks + self.statistics.chords
+    }
+
+    pub fn moves_remaining(&self) -> Option<u32> {
+        self.rules.move_limit.map(|limit| limit.saturating_sub(self.moves()))
+    }
+
+    fn is_out_of_moves(&self) -> bool {
+        self.rules.move_limit.is_some_and(|limit| self.moves() > limit)
+    }
+
+    /// Time since the first action, stopped when the game is finished.
+    pub fn elapsed(&self) -> Duration {
+        let played = match self.started_at {
+            Some(started_at) => self.ended_at.unwrap_or_else(|| self.clock.now()).saturating_sub(started_at),
+            None => Duration::ZERO,
+        };
+        let elapsed = self.resumed_elapsed.saturating_add(played);
+        match self.rules.time_limit {
+            Some(limit) => elapsed.min(limit),
+            None => elapsed,
         }
-        let mut is_win = true;
-        self.board.for_each_cell(|_, cell, stop| {
-            if cell.is_mine() && cell.cleared {
-                is_win = false;
-                *stop = true;
-            }
-        });
-        is_win
+    }
+
+    pub fn time_remaining(&self) -> Option<Duration> {
+        self.rules.time_limit.map(|limit| limit - self.elapsed())
+    }
+
+    fn start_clock(&mut self) {
+        if self.started_at.is_none() {
+            self.started_at = Some(self.clock.now());
+        }
+    }
+
+    /// Also restarts the clock when removing a flag undoes a win, so the
+    /// time limit keeps applying.
+    fn stop_clock_if_finished(&mut self) {
+        if !self.is_win() && !self.is_game_over() {
+            self.ended_at = None;
+        } else if self.ended_at.is_none() {
+            self.ended_at = Some(self.clock.now());
+        }
+    }
+
+    pub fn loss(&self) -> Option<Loss> {
+        if self.lives_remaining() == 0 {
+            return Some(Loss::MineHit);
+        }
+        if self.is_out_of_moves() {
+            return Some(Loss::OutOfMoves);
+        }
+        if self.remaining_mines() > 0 && self.time_remaining() == Some(Duration::ZERO) {
+            return Some(Loss::TimeUp);
+        }
+        None
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.loss().is_some()
+    }
+
+    pub fn is_win(&self) -> bool {
+        self.remaining_mines() == 0 && !self.is_game_over()
     }
 
     pub fn clear_all_non_mines(&mut self) {
@@ -194,6 +470,9 @@ impl Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::ManualClock;
+    use crate::render::Renderer;
+    use crate::topology::{Edges, Hexagonal, Neighbourhood};
 
     #[test]
     fn test_is_gameover() {
@@ -212,12 +491,89 @@ mod tests {
         let mut game = Game::new(Difficulty::Easy);
         game.board.for_each_cell_mut(|_, cell, _| {
             if cell.is_mine() {
-                cell.flagged = true;
+                cell.mark = Mark::Flag(cell.mines);
             }
         });
         assert!(game.is_win());
     }
 
+    #[test]
+    fn test_cycle_mark() {
+        let mut game = Game::new(Difficulty::Easy);
+        let coordinates = Point::zero();
+        let mark_at = |game: &Game| game.board.cell_at(coordinates).unwrap().mark;
+
+        game.cycle_mark(coordinates);
+        assert_eq!(mark_at(&game), Mark::Flag(1));
+        game.cycle_mark(coordinates);
+        assert_eq!(mark_at(&game), Mark::Question);
+        game.cycle_mark(coordinates);
+        assert_eq!(mark_at(&game), Mark::None);
+
+        game.rules.question_marks = false;
+        game.cycle_mark(coordinates);
+        assert_eq!(mark_at(&game), Mark::Flag(1));
+        game.cycle_mark(coordinates);
+        assert_eq!(mark_at(&game), Mark::None);
+    }
+
+    #[test]
+    fn test_question_marks_are_not_flags() {
+        let mut game = Game::new(Difficulty::Easy);
+        let mut mines = vec![];
+        game.board.for_each_cell(|point, cell, _| {
+            if cell.is_mine() {
+                mines.push(point);
+            }
+        });
+
+        mines.iter().for_each(|point| _ = game.toggle_flagged(*point));
+        assert_eq!(game.remaining_mines(), 0);
+        assert!(game.is_win());
+
+        game.cycle_mark(mines[0]);
+        assert_eq!(game.remaining_mines(), 1);
+        assert!(!game.is_win());
+    }
+
+    #[test]
+    fn test_flags_remaining() {
+        let mut board = Board::new_empty(Size { width: 3, height: 3 });
+        let mine_coordinates = Point { x: 0, y: 0 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        assert_eq!(game.flags_remaining(), 1);
+
+        game.toggle_flagged(Point { x: 2, y: 2 });
+        assert_eq!(game.flags_remaining(), 0);
+
+        game.toggle_flagged(mine_coordinates);
+        assert_eq!(game.flags_remaining(), -1);
+
+        game.cycle_mark(Point { x: 2, y: 1 });
+        game.cycle_mark(Point { x: 2, y: 1 });
+        assert_eq!(game.flags_remaining(), -1);
+    }
+
+    #[test]
+    fn test_wrong_flags_do_not_leak_mines() {
+        let mut board = Board::new_empty(Size { width: 3, height: 3 });
+        let mine_coordinates = Point { x: 0, y: 0 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        let mut right = Game::new_with_board(board.clone(), Difficulty::Easy);
+        let mut wrong = Game::new_with_board(board, Difficulty::Easy);
+
+        right.toggle_flagged(mine_coordinates);
+        wrong.toggle_flagged(Point { x: 2, y: 2 });
+
+        assert_eq!(right.flags_remaining(), wrong.flags_remaining());
+        assert_eq!(right.remaining_mines(), 0);
+        assert_eq!(wrong.remaining_mines(), 1);
+    }
+
     #[test]
     fn test_clear_white_cells() {
         let mut board = Board::new_empty(Size { width: 5, height: 5 });
@@ -225,17 +581,13 @@ mod tests {
         board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
         board.add_cell_numbers();
 
-        let mut game = Game {
-            board,
-            total_mines: 1,
-            difficulty: Difficulty::Easy,
-        };
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
 
         game.selected_at(Point { x: 0, y: 4 });
 
         println!(" ");
 
-        print_board(&game.board);
+        println!("{}", Renderer::new().render_view(&game.player_view()));
 
         game.board.for_each_cell(|_, cell, _| {
             if cell.is_mine() {
@@ -247,29 +599,354 @@ mod tests {
     }
 
     #[test]
-    fn test_difficulty_to_string() {
-        let diff = Difficulty::Easy;
-        assert_eq!(diff.to_string(), "Easy");
+    fn test_chord_at() {
+        let mut board = Board::new_empty(Size { width: 3, height: 3 });
+        let mine_coordinates = Point { x: 0, y: 0 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        game.selected_at(Point { x: 1, y: 1 });
+        assert!(game.chord_at(Point { x: 1, y: 1 }).is_empty());
+
+        game.toggle_flagged(mine_coordinates);
+        let cleared = game.chord_at(Point { x: 1, y: 1 });
+
+        assert_eq!(cleared.len(), 7);
+        assert!(!game.is_game_over());
+        game.board.for_each_cell(|_, cell, _| {
+            assert_eq!(cell.cleared, !cell.is_mine());
+        });
     }
 
-    fn print_board(board: &Board) {
-        let mut last_row = 0;
-        board.for_each_cell(|coordinates, cell, _| {
-            if last_row != coordinates.x {
-                last_row = coordinates.x;
-                println!(" ");
+    #[test]
+    fn test_statistics() {
+        let mut board = Board::new_empty(Size { width: 3, height: 3 });
+        let mine_coordinates = Point { x: 0, y: 0 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        game.selected_at(Point { x: 1, y: 1 });
+        game.selected_at(Point { x: 1, y: 1 });
+        game.toggle_flagged(Point { x: 2, y: 2 });
+        game.toggle_flagged(Point { x: 2, y: 2 });
+        game.toggle_flagged(mine_coordinates);
+        game.chord_at(Point { x: 1, y: 1 });
+        game.selected_at(Point { x: 9, y: 9 });
+
+        assert_eq!(
+            game.statistics,
+            Statistics {
+                left_clicks: 2,
+                right_clicks: 3,
+                chords: 1,
+                wasted_clicks: 2,
             }
-            let number = cell.number;
-            if !cell.cleared {
-                print!("[ \u{25AE}]");
-            } else if number == -1 {
-                print!("[ *]");
-            } else if number == 0 {
-                print!("[  ]");
-            } else {
-                print!("[ {}]", number);
+        );
+
+        let efficiency = game.efficiency(Duration::from_secs(2));
+        assert_eq!(efficiency.bbbv, 1);
+        assert_eq!(efficiency.bbbv_per_second, 0.5);
+        assert_eq!(efficiency.ioe, 1.0 / 6.0);
+        assert_eq!(efficiency.throughput, 0.25);
+    }
+
+    #[test]
+    fn test_clear_white_cells_hexagonal() {
+        let mut board = Board::new_empty(Size { width: 5, height: 5 }).with_topology(Hexagonal);
+        let mine_coordinates = Point { x: 2, y: 2 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        game.selected_at(Point { x: 0, y: 4 });
+
+        game.board.for_each_cell(|_, cell, _| {
+            assert_eq!(cell.cleared, !cell.is_mine());
+        });
+    }
+
+    #[test]
+    fn test_clear_white_cells_wrapping() {
+        let mut board = Board::new_empty(Size { width: 6, height: 6 }).with_edges(Edges::Wrapping);
+        let mine_coordinates = Point { x: 3, y: 3 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        game.selected_at(Point::zero());
+
+        game.board.for_each_cell(|_, cell, _| {
+            assert_eq!(cell.cleared, !cell.is_mine());
+        });
+    }
+
+    #[test]
+    fn test_clear_white_cells_cross() {
+        let mut board = Board::new_empty(Size { width: 3, height: 3 }).with_topology(Neighbourhood::cross());
+        let mine_coordinates = Point { x: 1, y: 1 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        // Corners are empty but only touch numbers, so the cascade stops there.
+        game.selected_at(Point::zero());
+
+        let mut cleared = vec![];
+        game.board.for_each_cell(|point, cell, _| {
+            if cell.cleared {
+                cleared.push(point);
             }
         });
-        println!(" ");
+        assert_eq!(cleared, vec![Point::zero(), Point { x: 0, y: 1 }, Point { x: 1, y: 0 }]);
+    }
+
+    #[test]
+    fn test_masked_board() {
+        let board = Board::from_text(
+            "
+            ....#
+            .##.#
+            ....*
+            ",
+        )
+        .unwrap();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        game.selected_at(Point::zero());
+        game.selected_at(Point { x: 1, y: 1 });
+        game.toggle_flagged(Point { x: 0, y: 4 });
+        game.clear_all_non_mines();
+
+        assert_eq!(game.total_mines, 1);
+        assert!(!game.board.cells.data[1][1].cleared);
+        assert!(!game.board.cells.data[0][4].is_flagged());
+        assert_eq!(game.board.cell_at(Point { x: 2, y: 3 }).unwrap().number, 1);
+        assert_eq!(game.board.cell_at(Point { x: 1, y: 0 }).unwrap().number, 0);
+
+        game.toggle_flagged(Point { x: 2, y: 4 });
+        assert!(game.is_win());
+    }
+
+    #[test]
+    fn test_cubic_game() {
+        let size = Size3 { width: 4, height: 4, depth: 4 };
+        let mut board = Board::new_cubic(0, size);
+        let mine_coordinates = Point3 { x: 3, y: 3, z: 3 }.flatten(size);
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        game.selected_at(Point3 { x: 0, y: 0, z: 0 }.flatten(size));
+        assert!(!game.is_game_over());
+        game.board.for_each_cell(|_, cell, _| {
+            assert_eq!(cell.cleared, !cell.is_mine());
+        });
+
+        game.toggle_flagged(mine_coordinates);
+        assert!(game.is_win());
+    }
+
+    #[test]
+    fn test_multi_mine_game() {
+        let board = Board::from_text("2..\n...\n..3").unwrap();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        assert_eq!(game.total_mines, 5);
+        assert_eq!(game.selected_at(Point { x: 1, y: 1 }).number, 5);
+
+        game.add_flag(Point::zero());
+        assert_eq!(game.flags_remaining(), 4);
+        assert_eq!(game.chord_at(Point { x: 1, y: 1 }).len(), 0);
+
+        game.add_flag(Point::zero());
+        assert_eq!(game.board.cell_at(Point::zero()).unwrap().mark, Mark::Flag(2));
+        assert!(game.add_flag(Point::zero()));
+        assert!(!game.add_flag(Point::zero()));
+        assert!(game.remove_flag(Point::zero()));
+        assert!(!game.remove_flag(Point { x: 0, y: 2 }));
+        // Flags are capped to the board's mines per cell.
+        (0..4).for_each(|_| _ = game.add_flag(Point { x: 2, y: 2 }));
+        assert_eq!(game.board.cell_at(Point { x: 2, y: 2 }).unwrap().mark, Mark::Flag(3));
+        assert_eq!(game.flags_remaining(), 0);
+        assert!(game.is_win());
+
+        game.remove_flag(Point { x: 2, y: 2 });
+        assert_eq!(game.board.cell_at(Point { x: 2, y: 2 }).unwrap().mark, Mark::Flag(2));
+        assert!(!game.is_win());
+
+        game.add_flag(Point { x: 2, y: 2 });
+        assert_eq!(game.chord_at(Point { x: 1, y: 1 }).len(), 6);
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn test_selected_mine_does_not_cascade() {
+        let board = Board::from_text("*..\n...\n...").unwrap();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+
+        game.selected_at(Point::zero());
+
+        let mut cleared = 0;
+        game.board.for_each_cell(|_, cell, _| {
+            if cell.cleared {
+                cleared += 1;
+            }
+        });
+        assert_eq!(cleared, 1);
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn test_lives() {
+        let board = Board::from_text("*..\n...\n..*").unwrap();
+        let rules = Rules { lives: 2, ..Rules::default() };
+        let mut game = Game::new_with_board(board, Difficulty::Easy).with_rules(rules);
+
+        game.selected_at(Point::zero());
+
+        let cell = game.board.cell_at(Point::zero()).unwrap();
+        assert!(cell.cleared && cell.is_flagged());
+        assert_eq!(game.lives_remaining(), 1);
+        assert_eq!(game.flags_remaining(), 1);
+        assert!(!game.is_game_over());
+        assert_eq!(
+            game.take_events(),
+            vec![GameEvent::MineHit {
+                coordinates: Point::zero(),
+                lives_remaining: 1
+            }]
+        );
+        assert!(game.take_events().is_empty());
+
+        assert!(!game.toggle_flagged(Point::zero()));
+        assert!(game.board.cell_at(Point::zero()).unwrap().is_flagged());
+
+        assert!(game.toggle_flagged(Point { x: 2, y: 2 }));
+        assert!(game.is_win());
+        assert!(game.toggle_flagged(Point { x: 2, y: 2 }));
+
+        game.selected_at(Point { x: 2, y: 2 });
+        assert!(!game.board.cell_at(Point { x: 2, y: 2 }).unwrap().is_flagged());
+        assert_eq!(game.lives_remaining(), 0);
+        assert!(game.is_game_over());
+        assert!(!game.is_win());
+        assert_eq!(
+            game.take_events(),
+            vec![GameEvent::MineHit {
+                coordinates: Point { x: 2, y: 2 },
+                lives_remaining: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_flag_limit() {
+        let board = Board::from_text("*..\n...\n..*").unwrap();
+        let rules = Rules {
+            flag_limit: FlagLimit::Mines,
+            ..Rules::default()
+        };
+        let mut game = Game::new_with_board(board, Difficulty::Easy).with_rules(rules);
+
+        assert_eq!(game.flag_limit(), Some(2));
+        assert!(game.toggle_flagged(Point { x: 0, y: 1 }));
+        assert!(game.cycle_mark(Point { x: 0, y: 2 }));
+        assert_eq!(game.flags_remaining(), 0);
+
+        assert!(!game.toggle_flagged(Point { x: 1, y: 1 }));
+        assert!(!game.cycle_mark(Point { x: 1, y: 1 }));
+        assert!(!game.board.cell_at(Point { x: 1, y: 1 }).unwrap().is_flagged());
+        assert_eq!(game.flags_remaining(), 0);
+
+        assert!(game.cycle_mark(Point { x: 0, y: 2 }));
+        assert_eq!(game.flags_remaining(), 1);
+        assert!(game.toggle_flagged(Point { x: 1, y: 1 }));
+
+        let rules = Rules {
+            flag_limit: FlagLimit::Count(1),
+            ..Rules::default()
+        };
+        let game = Game::new_with_board(Board::from_text("*..\n...\n..*").unwrap(), Difficulty::Easy).with_rules(rules);
+        assert_eq!(game.flags_remaining(), 1);
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let clock = Arc::new(ManualClock::default());
+        let rules = Rules {
+            time_limit: Some(Duration::from_secs(20)),
+            ..Rules::default()
+        };
+        let board = Board::from_text("*..\n...\n...").unwrap();
+        let mut game = Game::new_with_board(board, Difficulty::Easy).with_rules(rules).with_clock(clock.clone());
+
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(game.elapsed(), Duration::ZERO);
+
+        game.selected_at(Point { x: 2, y: 2 });
+        clock.advance(Duration::from_secs(15));
+        assert_eq!(game.time_remaining(), Some(Duration::from_secs(5)));
+        assert!(!game.is_game_over());
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(game.elapsed(), Duration::from_secs(20));
+        assert_eq!(game.loss(), Some(Loss::TimeUp));
+        assert!(!game.toggle_flagged(Point::zero()));
+        assert!(!game.is_win());
+    }
+
+    #[test]
+    fn test_clock_stops_on_win() {
+        let clock = Arc::new(ManualClock::default());
+        let rules = Rules {
+            time_limit: Some(Duration::from_secs(20)),
+            ..Rules::default()
+        };
+        let board = Board::from_text("*..\n...\n...").unwrap();
+        let mut game = Game::new_with_board(board, Difficulty::Easy).with_rules(rules).with_clock(clock.clone());
+
+        game.selected_at(Point { x: 2, y: 2 });
+        clock.advance(Duration::from_secs(12));
+        game.toggle_flagged(Point::zero());
+        clock.advance(Duration::from_secs(30));
+
+        assert!(game.is_win());
+        assert_eq!(game.loss(), None);
+        assert_eq!(game.elapsed(), Duration::from_secs(12));
+
+        // Taking the flag back resumes the game, and the time already ran out.
+        assert!(game.toggle_flagged(Point::zero()));
+        assert!(!game.is_win());
+        assert_eq!(game.elapsed(), Duration::from_secs(20));
+        assert_eq!(game.loss(), Some(Loss::TimeUp));
+        assert!(!game.selected_at(Point { x: 0, y: 1 }).cleared);
+    }
+
+    #[test]
+    fn test_move_limit() {
+        let rules = Rules {
+            move_limit: Some(2),
+            ..Rules::default()
+        };
+        let board = Board::from_text("*..\n...\n..*").unwrap();
+        let mut game = Game::new_with_board(board, Difficulty::Easy).with_rules(rules);
+
+        game.selected_at(Point { x: 0, y: 1 });
+        game.selected_at(Point { x: 1, y: 0 });
+        assert_eq!(game.moves_remaining(), Some(0));
+        assert!(!game.is_game_over());
+
+        game.selected_at(Point { x: 1, y: 1 });
+        assert!(!game.board.cell_at(Point { x: 1, y: 1 }).unwrap().cleared);
+        assert_eq!(game.loss(), Some(Loss::OutOfMoves));
+        assert!(game.is_game_over());
+    }
+
+    #[test]
+    fn test_difficulty_to_string() {
+        let diff = Difficulty::Easy;
+        assert_eq!(diff.to_string(), "Easy");
     }
 }
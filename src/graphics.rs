@@ -1,8 +1,11 @@
 use std::mem;
 
 use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Vec2<T> {
     pub data: Vec<Vec<T>>,
 }
@@ -53,7 +56,7 @@ impl<T> Vec2<T> {
     }
 
     pub fn get_height(&self) -> usize {
-        let row = self.data.get(0);
+        let row = self.data.first();
         match row {
             Some(cell) => cell.len(),
             None => 0,
@@ -71,9 +74,16 @@ impl<T> Vec2<T> {
         // TODO: make it safe
         _ = mem::replace(&mut self.data[coordinates.x][coordinates.y], element);
     }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Vec2<U> {
+        Vec2 {
+            data: self.data.iter().map(|row| row.iter().map(&f).collect()).collect(),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Point {
     pub x: usize,
     pub y: usize,
@@ -84,9 +94,14 @@ impl Point {
         range_x: std::ops::Range<usize>,
         range_y: std::ops::Range<usize>,
     ) -> Point {
-        use rand::prelude::thread_rng;
-        let mut rng = thread_rng();
+        Point::random_between_with(&mut rand::thread_rng(), range_x, range_y)
+    }
 
+    pub fn random_between_with(
+        rng: &mut impl Rng,
+        range_x: std::ops::Range<usize>,
+        range_y: std::ops::Range<usize>,
+    ) -> Point {
         Point {
             x: rng.gen_range(range_x),
             y: rng.gen_range(range_y),
@@ -98,8 +113,115 @@ impl Point {
     }
 }
 
+/// Point on a board without bounds, that can go in any direction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SignedPoint {
+    pub x: i64,
+    pub y: i64,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Size {
     pub width: usize,
     pub height: usize,
 }
+
+/// Grid of `depth` layers, each one a `Vec2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vec3<T> {
+    pub data: Vec<Vec2<T>>,
+}
+
+impl<T> Vec3<T> {
+    pub fn get_width(&self) -> usize {
+        self.data.first().map_or(0, |layer| layer.get_width())
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.data.first().map_or(0, |layer| layer.get_height())
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn get_size(&self) -> Size3 {
+        Size3 {
+            width: self.get_width(),
+            height: self.get_height(),
+            depth: self.get_depth(),
+        }
+    }
+
+    pub fn get_element(&self, coordinates: Point3) -> Option<&T> {
+        self.data.get(coordinates.z)?.get_element(Point { x: coordinates.x, y: coordinates.y })
+    }
+
+    pub fn for_each_element(&self, mut f: impl FnMut(Point3, &T, &mut bool)) {
+        let mut stop = false;
+        for (z, layer) in self.data.iter().enumerate() {
+            layer.for_each_element(|point, element, layer_stop| {
+                f(Point3 { x: point.x, y: point.y, z }, element, &mut stop);
+                *layer_stop = stop;
+            });
+            if stop {
+                break;
+            }
+        }
+    }
+
+    /// Lays the layers one after the other along `x`,
+    /// see `Point3::flatten`.
+    pub fn flatten(self) -> Vec2<T> {
+        Vec2 {
+            data: self.data.into_iter().flat_map(|layer| layer.data).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Point3 {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+}
+
+impl Point3 {
+    /// Position of the point in a 2D grid where the layers are laid out
+    /// one after the other along `x`.
+    pub fn flatten(&self, size: Size3) -> Point {
+        Point {
+            x: self.z * size.width + self.x,
+            y: self.y,
+        }
+    }
+
+    pub fn unflatten(point: Point, size: Size3) -> Point3 {
+        Point3 {
+            x: point.x % size.width,
+            y: point.y,
+            z: point.x / size.width,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Size3 {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+}
+
+impl Size3 {
+    pub fn flatten(&self) -> Size {
+        Size {
+            width: self.width * self.depth,
+            height: self.height,
+        }
+    }
+}
@@ -1,12 +1,46 @@
 mod board;
 mod cell;
+mod clock;
+mod encoding;
+mod endless;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod game;
 mod graphics;
+#[cfg(feature = "python")]
+pub mod python;
+mod render;
+mod rng;
+mod rules;
+mod save;
+#[cfg(feature = "server")]
+pub mod server;
+mod session;
+mod stats;
+mod svg;
+mod topology;
+mod versus;
+mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use board::Board;
-pub use cell::Cell;
-pub use game::{Difficulty, Game};
-pub use graphics::{Point, Size, Vec2};
+pub use cell::{Cell, Mark};
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use encoding::ENCODING_VERSION;
+pub use endless::{EndlessBoard, EndlessGame, CHUNK_SIZE};
+pub use game::{Difficulty, Game, GameEvent};
+pub use graphics::{Point, Point3, SignedPoint, Size, Size3, Vec2, Vec3};
+pub use render::{GlyphSet, Renderer};
+pub use rng::SeededRng;
+pub use rules::{FlagLimit, Loss, Rules};
+pub use save::SAVE_VERSION;
+pub use session::{Action, Delta, Outcome, PlayerId, Session};
+pub use stats::{Efficiency, Statistics};
+pub use svg::SvgRenderer;
+pub use topology::{Cubic, Edges, Hex, Hexagonal, Neighbourhood, Square, Topology};
+pub use versus::{Move, Scoring, VersusGame};
+pub use view::{PlayerView, VisibleCell};
 
 #[cfg(test)]
 mod tests {
@@ -16,7 +50,7 @@ mod tests {
     fn test_drawing_hard() {
         let mut game = Game::new(Difficulty::Hard);
         game.clear_all();
-        print_board(game.board);
+        println!("{}", Renderer::new().render_board(&game.board));
     }
 
     #[test]
@@ -36,31 +70,8 @@ mod tests {
             }
         }
         let game_over = game.is_game_over();
-        // print_board(game.board);
+        // println!("{}", Renderer::new().render_view(&game.player_view()));
 
         assert!(game_over);
     }
-
-    fn print_board(board: Board) {
-        for x in 0..board.get_width() {
-            for y in 0..board.get_height() {
-                match board.cell_at(Point { x, y }) {
-                    Some(cell) => {
-                        let number = cell.number;
-                        if !cell.cleared {
-                            print!("[XX]");
-                        } else if number == -1 {
-                            print!("[ *]");
-                        } else if number == 0 {
-                            print!("[  ]");
-                        } else {
-                            print!("[ {}]", number);
-                        }
-                    }
-                    None => continue,
-                }
-            }
-            println!(" ");
-        }
-    }
 }
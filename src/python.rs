@@ -0,0 +1,229 @@
+//! Python module, built with maturin from `pyproject.toml`.
+//!
+//! ```python
+//! import numpy as np
+//! from minesweeper_core import Game
+//!
+//! game = Game("medium", seed=7)
+//! game.selected_at(8, 8)
+//! cells = np.array(game.view())
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::board;
+use crate::game::{self, Difficulty};
+use crate::graphics::{Point, Size};
+use crate::rng::SeededRng;
+use crate::view::VisibleCell;
+
+/// Largest board `Board.generate` creates, so that huge sizes raise
+/// instead of aborting on allocation.
+const MAX_CELLS: usize = 1 << 20;
+
+// Codes used by `Game.view()` for cells without a number.
+const HIDDEN: i8 = -1;
+const FLAGGED: i8 = -2;
+const QUESTIONED: i8 = -3;
+const MINE: i8 = -4;
+const VOID: i8 = -5;
+
+fn code(cell: VisibleCell) -> i8 {
+    match cell {
+        VisibleCell::Hidden => HIDDEN,
+        VisibleCell::Flagged(_) => FLAGGED,
+        VisibleCell::Questioned => QUESTIONED,
+        VisibleCell::Revealed(number) => number,
+        VisibleCell::Mine => MINE,
+        VisibleCell::Void => VOID,
+    }
+}
+
+fn difficulty(name: &str) -> PyResult<Difficulty> {
+    match name {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        _ => Err(PyValueError::new_err(format!("unknown difficulty {name}"))),
+    }
+}
+
+#[pyclass(module = "minesweeper_core")]
+#[derive(Clone)]
+pub struct Board {
+    board: board::Board,
+}
+
+#[pymethods]
+impl Board {
+    /// Board with `mines` placed at random, or from `seed` when given.
+    #[staticmethod]
+    #[pyo3(signature = (width, height, mines, seed=None))]
+    fn generate(width: usize, height: usize, mines: u32, seed: Option<u64>) -> PyResult<Board> {
+        let cells = width.checked_mul(height).filter(|cells| *cells <= MAX_CELLS);
+        let cells = cells.ok_or_else(|| PyValueError::new_err("the board is too large"))?;
+        if mines as usize >= cells {
+            return Err(PyValueError::new_err("the mines do not fit in the board"));
+        }
+        let size = Size { width, height };
+        let board = match seed {
+            Some(seed) => board::Board::new_with_rng(mines as i32, size, &mut SeededRng::new(seed)),
+            None => board::Board::new(mines as i32, size),
+        };
+        Ok(Board { board })
+    }
+
+    #[staticmethod]
+    fn from_text(text: &str) -> PyResult<Board> {
+        let board = board::Board::from_text(text).ok_or_else(|| PyValueError::new_err("invalid board"))?;
+        Ok(Board { board })
+    }
+
+    fn to_text(&self) -> String {
+        self.board.to_text()
+    }
+
+    #[getter]
+    fn width(&self) -> usize {
+        self.board.get_width()
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        self.board.get_height()
+    }
+
+    /// Mines in each cell, row by row.
+    fn mines(&self) -> Vec<Vec<u8>> {
+        self.board.cells.map(|cell| cell.mines).data
+    }
+
+    /// Neighbouring mines of each cell, row by row.
+    fn numbers(&self) -> Vec<Vec<i8>> {
+        self.board.cells.map(|cell| cell.number).data
+    }
+
+    fn bbbv(&self) -> u32 {
+        self.board.bbbv()
+    }
+
+    fn __str__(&self) -> String {
+        self.board.to_text()
+    }
+}
+
+#[pyclass(module = "minesweeper_core")]
+pub struct Game {
+    game: game::Game,
+}
+
+#[pymethods]
+impl Game {
+    #[new]
+    #[pyo3(signature = (difficulty="easy", seed=None))]
+    fn new(difficulty: &str, seed: Option<u64>) -> PyResult<Game> {
+        let difficulty = self::difficulty(difficulty)?;
+        let game = match seed {
+            Some(seed) => game::Game::new_with_rng(difficulty, &mut SeededRng::new(seed)),
+            None => game::Game::new(difficulty),
+        };
+        Ok(Game { game })
+    }
+
+    #[staticmethod]
+    fn from_board(board: &Board) -> Game {
+        Game {
+            game: game::Game::new_with_board(board.board.clone(), Difficulty::Easy),
+        }
+    }
+
+    #[getter]
+    fn board(&self) -> Board {
+        Board {
+            board: self.game.board.clone(),
+        }
+    }
+
+    /// Reveals a cell and returns its code in `view`. Flagged cells are
+    /// left hidden.
+    fn selected_at(&mut self, x: usize, y: usize) -> Option<i8> {
+        let coordinates = Point { x, y };
+        if !self.game.board.cell_at(coordinates)?.is_flagged() {
+            self.game.selected_at(coordinates);
+        }
+        self.game.player_view().cell_at(coordinates).map(code)
+    }
+
+    fn toggle_flagged(&mut self, x: usize, y: usize) -> bool {
+        self.game.toggle_flagged(Point { x, y })
+    }
+
+    fn cycle_mark(&mut self, x: usize, y: usize) -> bool {
+        self.game.cycle_mark(Point { x, y })
+    }
+
+    /// Returns how many cells were revealed.
+    fn chord_at(&mut self, x: usize, y: usize) -> usize {
+        self.game.chord_at(Point { x, y }).len()
+    }
+
+    /// One of `"playing"`, `"won"` or `"lost"`.
+    #[getter]
+    fn state(&self) -> &'static str {
+        if self.game.is_game_over() {
+            "lost"
+        } else if self.game.is_win() {
+            "won"
+        } else {
+            "playing"
+        }
+    }
+
+    #[getter]
+    fn flags_remaining(&self) -> i32 {
+        self.game.flags_remaining()
+    }
+
+    /// What the player sees, row by row: the number of revealed cells or
+    /// one of the negative codes exported by the module.
+    fn view(&self) -> Vec<Vec<i8>> {
+        self.game.player_view().rows().map(|row| row.iter().map(|cell| code(*cell)).collect()).collect()
+    }
+
+    fn statistics(&self) -> HashMap<&'static str, u32> {
+        let statistics = self.game.statistics;
+        HashMap::from([
+            ("left_clicks", statistics.left_clicks),
+            ("right_clicks", statistics.right_clicks),
+            ("chords", statistics.chords),
+            ("wasted_clicks", statistics.wasted_clicks),
+        ])
+    }
+
+    /// Arbiter metrics for a game played in `seconds`.
+    fn efficiency(&self, seconds: f64) -> HashMap<&'static str, f64> {
+        let efficiency = self.game.efficiency(Duration::from_secs_f64(seconds.max(0.0)));
+        HashMap::from([
+            ("bbbv", efficiency.bbbv as f64),
+            ("bbbv_per_second", efficiency.bbbv_per_second),
+            ("ioe", efficiency.ioe),
+            ("throughput", efficiency.throughput),
+        ])
+    }
+}
+
+#[pymodule]
+fn minesweeper_core(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Board>()?;
+    module.add_class::<Game>()?;
+    module.add("HIDDEN", HIDDEN)?;
+    module.add("FLAGGED", FLAGGED)?;
+    module.add("QUESTIONED", QUESTIONED)?;
+    module.add("MINE", MINE)?;
+    module.add("VOID", VOID)?;
+    Ok(())
+}
@@ -0,0 +1,244 @@
+use crate::board::Board;
+use crate::cell::{Cell, Mark};
+use crate::graphics::Point;
+use crate::view::{PlayerView, VisibleCell};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum GlyphSet {
+    #[default]
+    Ascii,
+    /// Unicode symbols, framed with box drawing characters.
+    Unicode,
+    /// Emoji, two terminal columns wide each.
+    Emoji,
+}
+
+/// Turns boards into text, one line per row like `Board::to_text`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Renderer {
+    pub glyphs: GlyphSet,
+    /// Wraps numbers, flags and mines in ANSI color codes.
+    pub colors: bool,
+    /// Labels rows and columns with their index.
+    pub coordinates: bool,
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Renderer::default()
+    }
+
+    pub fn with_glyphs(self, glyphs: GlyphSet) -> Self {
+        Renderer { glyphs, ..self }
+    }
+
+    pub fn with_colors(self, colors: bool) -> Self {
+        Renderer { colors, ..self }
+    }
+
+    pub fn with_coordinates(self, coordinates: bool) -> Self {
+        Renderer { coordinates, ..self }
+    }
+
+    /// Renders what the player can see.
+    pub fn render_view(&self, view: &PlayerView) -> String {
+        let rows: Vec<Vec<VisibleCell>> = view.rows().map(|row| row.to_vec()).collect();
+        self.render(&rows)
+    }
+
+    /// Renders the whole board, as if every cell was revealed.
+    pub fn render_board(&self, board: &Board) -> String {
+        let rows: Vec<Vec<VisibleCell>> = board
+            .cells
+            .data
+            .iter()
+            .enumerate()
+            .map(|(x, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(y, cell)| {
+                        if !board.contains(Point { x, y }) {
+                            return VisibleCell::Void;
+                        }
+                        VisibleCell::from(&Cell {
+                            cleared: true,
+                            mark: Mark::None,
+                            ..*cell
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+        self.render(&rows)
+    }
+
+    fn render(&self, rows: &[Vec<VisibleCell>]) -> String {
+        let columns = rows.first().map_or(0, |row| row.len());
+        let glyphs: Vec<Vec<String>> = rows.iter().map(|row| row.iter().map(|cell| self.glyph(*cell)).collect()).collect();
+
+        let (cell_width, separator) = match self.glyphs {
+            GlyphSet::Emoji => (2, ""),
+            GlyphSet::Ascii | GlyphSet::Unicode => {
+                let widest_glyph = glyphs.iter().flatten().map(|glyph| glyph.chars().count()).max().unwrap_or(1);
+                let widest_label = if self.coordinates { digits(columns) } else { 1 };
+                (widest_glyph.max(widest_label), " ")
+            }
+        };
+        let label_width = digits(rows.len());
+        let framed = self.glyphs == GlyphSet::Unicode;
+        let row_width = columns * cell_width + columns.saturating_sub(1) * separator.len();
+        let margin = if self.coordinates { " ".repeat(label_width + 1) } else { String::new() };
+
+        let mut lines = vec![];
+        if self.coordinates {
+            let labels: Vec<String> = (0..columns).map(|y| format!("{y:>cell_width$}")).collect();
+            let inset = if framed { " " } else { "" };
+            lines.push(format!("{margin}{inset}{}", labels.join(separator)));
+        }
+        if framed {
+            lines.push(format!("{margin}┌{}┐", "─".repeat(row_width)));
+        }
+        for (x, row) in glyphs.iter().enumerate() {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&rows[x])
+                .map(|(glyph, cell)| {
+                    let glyph = match self.glyphs {
+                        GlyphSet::Emoji => glyph.clone(),
+                        GlyphSet::Ascii | GlyphSet::Unicode => format!("{glyph:>cell_width$}"),
+                    };
+                    self.paint(glyph, *cell)
+                })
+                .collect();
+            let mut line = if self.coordinates { format!("{x:>label_width$} ") } else { String::new() };
+            if framed {
+                line = format!("{line}│{}│", cells.join(separator));
+            } else {
+                line.push_str(&cells.join(separator));
+            }
+            lines.push(line);
+        }
+        if framed {
+            lines.push(format!("{margin}└{}┘", "─".repeat(row_width)));
+        }
+        lines.join("\n")
+    }
+
+    fn glyph(&self, cell: VisibleCell) -> String {
+        let glyph = match (self.glyphs, cell) {
+            (GlyphSet::Ascii, VisibleCell::Hidden) => "#",
+            (GlyphSet::Ascii, VisibleCell::Flagged(_)) => "F",
+            (GlyphSet::Ascii, VisibleCell::Mine) => "*",
+            (GlyphSet::Ascii, VisibleCell::Revealed(0)) => ".",
+            (GlyphSet::Unicode, VisibleCell::Hidden) => "■",
+            (GlyphSet::Unicode, VisibleCell::Flagged(_)) => "⚑",
+            (GlyphSet::Unicode, VisibleCell::Mine) => "✹",
+            (GlyphSet::Unicode, VisibleCell::Revealed(0)) => "·",
+            (GlyphSet::Ascii | GlyphSet::Unicode, VisibleCell::Questioned) => "?",
+            (GlyphSet::Ascii | GlyphSet::Unicode, VisibleCell::Void) => " ",
+            (GlyphSet::Emoji, VisibleCell::Hidden) => "⬜",
+            (GlyphSet::Emoji, VisibleCell::Flagged(_)) => "🚩",
+            (GlyphSet::Emoji, VisibleCell::Questioned) => "❓",
+            (GlyphSet::Emoji, VisibleCell::Mine) => "💣",
+            (GlyphSet::Emoji, VisibleCell::Revealed(0)) => "⬛",
+            (GlyphSet::Emoji, VisibleCell::Void) => "  ",
+            (GlyphSet::Emoji, VisibleCell::Revealed(number @ 1..=9)) => return format!("{number}\u{fe0f}\u{20e3}"),
+            (GlyphSet::Emoji, VisibleCell::Revealed(number)) => return format!("{number:>2}"),
+            (_, VisibleCell::Revealed(number)) => return number.to_string(),
+        };
+        glyph.to_string()
+    }
+
+    fn paint(&self, glyph: String, cell: VisibleCell) -> String {
+        if !self.colors {
+            return glyph;
+        }
+        let color = match cell {
+            VisibleCell::Revealed(number) => number_color(number),
+            VisibleCell::Flagged(_) | VisibleCell::Mine => Some("31"),
+            VisibleCell::Hidden | VisibleCell::Questioned | VisibleCell::Void => None,
+        };
+        match color {
+            Some(color) => format!("\x1b[{color}m{glyph}\x1b[0m"),
+            None => glyph,
+        }
+    }
+}
+
+/// ANSI foreground color for a cell number, like the classic game.
+fn number_color(number: i8) -> Option<&'static str> {
+    match number {
+        1 => Some("94"),
+        2 => Some("32"),
+        3 => Some("91"),
+        4 => Some("34"),
+        5 => Some("31"),
+        6 => Some("36"),
+        7 => Some("35"),
+        8 => Some("90"),
+        _ => None,
+    }
+}
+
+fn digits(count: usize) -> usize {
+    count.saturating_sub(1).to_string().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Difficulty, Game};
+
+    fn game() -> Game {
+        let board = Board::from_text("*..\n...").unwrap();
+        let mut game = Game::new_with_board(board, Difficulty::Easy);
+        game.selected_at(Point { x: 1, y: 2 });
+        game
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        let game = game();
+
+        assert_eq!(Renderer::new().render_view(&game.player_view()), "# 1 .\n# 1 .");
+        assert_eq!(Renderer::new().render_board(&game.board), "* 1 .\n1 1 .");
+        assert_eq!(
+            Renderer::new().with_coordinates(true).render_view(&game.player_view()),
+            "  0 1 2\n0 # 1 .\n1 # 1 ."
+        );
+    }
+
+    #[test]
+    fn test_render_unicode() {
+        let mut game = game();
+        game.toggle_flagged(Point::zero());
+        let renderer = Renderer::new().with_glyphs(GlyphSet::Unicode).with_coordinates(true);
+
+        assert_eq!(renderer.render_view(&game.player_view()), "   0 1 2\n  ┌─────┐\n0 │⚑ 1 ·│\n1 │■ 1 ·│\n  └─────┘");
+    }
+
+    #[test]
+    fn test_render_emoji() {
+        let renderer = Renderer::new().with_glyphs(GlyphSet::Emoji);
+
+        assert_eq!(renderer.render_view(&game().player_view()), "⬜1\u{fe0f}\u{20e3}⬛\n⬜1\u{fe0f}\u{20e3}⬛");
+    }
+
+    #[test]
+    fn test_render_colors() {
+        let board = Board::from_text("*#\n..").unwrap();
+        let rendered = Renderer::new().with_colors(true).render_board(&board);
+
+        assert_eq!(rendered, "\x1b[31m*\x1b[0m  \n\x1b[94m1\x1b[0m \x1b[94m1\x1b[0m");
+    }
+
+    #[test]
+    fn test_render_masked_board() {
+        let mut board = Board::from_text("*#\n..").unwrap();
+        // The mask decides, not coordinates stored in the cells.
+        board.cells.data[0][1].coordinates = Point::zero();
+        board.cells.data[1][0].coordinates = Point { x: 0, y: 1 };
+
+        assert_eq!(Renderer::new().render_board(&board), "*  \n1 1");
+    }
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Mines that can be hit before the game is over. Every hit mine
+    /// is revealed and flagged while there are lives left.
+    pub lives: u32,
+    pub question_marks: bool,
+    pub flag_limit: FlagLimit,
+    /// The game is lost when the clock reaches the limit.
+    pub time_limit: Option<Duration>,
+    /// The game is lost when trying to reveal more times than the limit.
+    pub move_limit: Option<u32>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum FlagLimit {
+    #[default]
+    Unlimited,
+    /// As many flags as there are mines.
+    Mines,
+    Count(u32),
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            lives: 1,
+            question_marks: true,
+            flag_limit: FlagLimit::Unlimited,
+            time_limit: None,
+            move_limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Loss {
+    MineHit,
+    TimeUp,
+    OutOfMoves,
+}
@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+use crate::board::Board;
+use crate::cell::{Cell, Mark};
+use crate::game::{Difficulty, Game};
+use crate::graphics::*;
+use crate::rules::{FlagLimit, Rules};
+use crate::stats::Statistics;
+use crate::topology::{topology_from_text, Edges};
+
+/// Version written by `Game::save`. `Game::load` reads every version up to
+/// this one, keys missing from older saves take their default value.
+pub const SAVE_VERSION: u32 = 1;
+
+const HEADER: &str = "minesweeper_core save";
+
+impl Game {
+    /// Writes the whole game as text: layout, revealed and marked cells,
+    /// rules, statistics and time played. Fails for boards with a topology
+    /// that can not be saved.
+    pub fn save(&self, mut writer: impl Write) -> io::Result<()> {
+        let topology = self
+            .board
+            .topology
+            .to_text()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the topology can not be saved"))?;
+        let mut state = String::new();
+        for row in &self.board.cells.data {
+            for cell in row {
+                state.push(state_char(cell).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the cells can not be saved"))?);
+            }
+            state.push('\n');
+        }
+        let rules = &self.rules;
+        let statistics = &self.statistics;
+
+        writeln!(writer, "{HEADER} {SAVE_VERSION}")?;
+        writeln!(writer, "difficulty {}", self.difficulty.to_string().to_lowercase())?;
+        writeln!(writer, "total_mines {}", self.total_mines)?;
+        writeln!(writer, "elapsed_ms {}", self.elapsed().as_millis())?;
+        writeln!(writer, "topology {topology}")?;
+        writeln!(writer, "edges {}", if self.board.edges == Edges::Wrapping { "wrapping" } else { "bounded" })?;
+        writeln!(writer, "mines_per_cell {}", self.board.mines_per_cell)?;
+        writeln!(writer, "lives {}", rules.lives)?;
+        writeln!(writer, "question_marks {}", rules.question_marks)?;
+        let flag_limit = match rules.flag_limit {
+            FlagLimit::Unlimited => "unlimited".to_string(),
+            FlagLimit::Mines => "mines".to_string(),
+            FlagLimit::Count(count) => count.to_string(),
+        };
+        writeln!(writer, "flag_limit {flag_limit}")?;
+        writeln!(writer, "time_limit_ms {}", optional(rules.time_limit.map(|limit| limit.as_millis())))?;
+        writeln!(writer, "move_limit {}", optional(rules.move_limit))?;
+        writeln!(
+            writer,
+            "statistics {} {} {} {}",
+            statistics.left_clicks, statistics.right_clicks, statistics.chords, statistics.wasted_clicks
+        )?;
+        writeln!(writer, "size {} {}", self.board.get_width(), self.board.get_height())?;
+        writeln!(writer, "layout")?;
+        writeln!(writer, "{}", self.board.to_text())?;
+        writeln!(writer, "state")?;
+        write!(writer, "{state}")
+    }
+
+    /// Reads a game written by `save`, with the clock paused until the
+    /// next action.
+    pub fn load(mut reader: impl Read) -> io::Result<Game> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let mut lines = text.lines();
+
+        let version = lines
+            .next()
+            .and_then(|line| line.strip_prefix(HEADER))
+            .and_then(|version| version.trim().parse::<u32>().ok())
+            .ok_or_else(|| invalid("not a saved game"))?;
+        if version == 0 || version > SAVE_VERSION {
+            return Err(invalid(&format!("unsupported save version {version}")));
+        }
+
+        let mut values = HashMap::new();
+        for line in lines.by_ref() {
+            if line == "layout" {
+                break;
+            }
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            values.insert(key, value);
+        }
+        let value = |key: &str| values.get(key).copied();
+
+        let size: Vec<usize> = value("size")
+            .ok_or_else(|| invalid("missing size"))?
+            .split_whitespace()
+            .filter_map(|number| number.parse().ok())
+            .collect();
+        let [width, height] = size[..] else {
+            return Err(invalid("invalid size"));
+        };
+        let layout: Vec<&str> = lines.by_ref().take(width).collect();
+        let mut board = Board::from_text(&layout.join("\n")).ok_or_else(|| invalid("invalid layout"))?;
+        if board.get_size() != (Size { width, height }) {
+            return Err(invalid("invalid layout"));
+        }
+        if let Some(topology) = value("topology") {
+            board.topology = topology_from_text(topology).ok_or_else(|| invalid("invalid topology"))?;
+        }
+        board.edges = match value("edges") {
+            None | Some("bounded") => Edges::Bounded,
+            Some("wrapping") => Edges::Wrapping,
+            Some(edges) => return Err(invalid(&format!("unknown edges {edges}"))),
+        };
+        board.mines_per_cell = parse(&values, "mines_per_cell")?.unwrap_or(board.mines_per_cell);
+        if !board.topology.supports(board.get_size(), board.edges) {
+            return Err(invalid("the topology does not support the board"));
+        }
+        if board.mines_per_cell > board.max_mines_per_cell() {
+            return Err(invalid("invalid mines_per_cell"));
+        }
+        board.add_cell_numbers();
+
+        if lines.next() != Some("state") {
+            return Err(invalid("missing state"));
+        }
+        for x in 0..width {
+            let line: Vec<char> = lines.next().ok_or_else(|| invalid("missing state"))?.chars().collect();
+            if line.len() != height {
+                return Err(invalid("invalid state"));
+            }
+            for (y, char) in line.into_iter().enumerate() {
+                let cell = &mut board.cells.data[x][y];
+                let (cleared, mark) = parse_state_char(char, cell.mines).ok_or_else(|| invalid("invalid state"))?;
+                cell.cleared = cleared;
+                cell.mark = mark;
+            }
+        }
+
+        let difficulty = match value("difficulty") {
+            None | Some("easy") => Difficulty::Easy,
+            Some("medium") => Difficulty::Medium,
+            Some("hard") => Difficulty::Hard,
+            Some(difficulty) => return Err(invalid(&format!("unknown difficulty {difficulty}"))),
+        };
+        let mut game = Game::new_with_board(board, difficulty);
+        game.total_mines = parse(&values, "total_mines")?.unwrap_or(game.total_mines);
+
+        let defaults = Rules::default();
+        game.rules = Rules {
+            lives: parse(&values, "lives")?.unwrap_or(defaults.lives),
+            question_marks: parse(&values, "question_marks")?.unwrap_or(defaults.question_marks),
+            flag_limit: match value("flag_limit") {
+                None | Some("unlimited") => FlagLimit::Unlimited,
+                Some("mines") => FlagLimit::Mines,
+                Some(_) => FlagLimit::Count(parse(&values, "flag_limit")?.unwrap_or_default()),
+            },
+            time_limit: parse_optional(value("time_limit_ms"))?.map(Duration::from_millis),
+            move_limit: parse_optional(value("move_limit"))?,
+        };
+        let elapsed = Duration::from_millis(parse(&values, "elapsed_ms")?.unwrap_or(0));
+        game.resumed_elapsed = game.rules.time_limit.map_or(elapsed, |limit| elapsed.min(limit));
+
+        if let Some(statistics) = value("statistics") {
+            let counts: Vec<u32> = statistics.split_whitespace().filter_map(|count| count.parse().ok()).collect();
+            let [left_clicks, right_clicks, chords, wasted_clicks] = counts[..] else {
+                return Err(invalid("invalid statistics"));
+            };
+            game.statistics = Statistics {
+                left_clicks,
+                right_clicks,
+                chords,
+                wasted_clicks,
+            };
+        }
+        Ok(game)
+    }
+}
+
+/// `-` hidden, `x` cleared, `?` question mark, `f` flag, `2` to `9` for
+/// several flags and `F` for mines hit with lives left, cleared and flagged
+/// once per mine. Cells the layout or this format can not hold give `None`.
+fn state_char(cell: &Cell) -> Option<char> {
+    if cell.mines > 9 {
+        return None;
+    }
+    match (cell.cleared, cell.mark) {
+        (true, Mark::None) => Some('x'),
+        (true, Mark::Flag(flags)) if flags == cell.mines && cell.is_mine() => Some('F'),
+        (true, _) => None,
+        (false, Mark::None) => Some('-'),
+        (false, Mark::Question) => Some('?'),
+        (false, Mark::Flag(1)) => Some('f'),
+        (false, Mark::Flag(flags @ 2..=9)) => char::from_digit(flags as u32, 10),
+        (false, Mark::Flag(_)) => None,
+    }
+}
+
+fn parse_state_char(char: char, mines: u8) -> Option<(bool, Mark)> {
+    match char {
+        'x' => Some((true, Mark::None)),
+        'F' if mines > 0 => Some((true, Mark::Flag(mines))),
+        '-' => Some((false, Mark::None)),
+        '?' => Some((false, Mark::Question)),
+        'f' => Some((false, Mark::Flag(1))),
+        '2'..='9' => Some((false, Mark::Flag(char.to_digit(10)? as u8))),
+        _ => None,
+    }
+}
+
+fn optional(value: Option<impl ToString>) -> String {
+    value.map_or("none".to_string(), |value| value.to_string())
+}
+
+fn parse<T: std::str::FromStr>(values: &HashMap<&str, &str>, key: &str) -> io::Result<Option<T>> {
+    values.get(key).map(|value| value.parse().map_err(|_| invalid(&format!("invalid {key}")))).transpose()
+}
+
+fn parse_optional<T: std::str::FromStr>(value: Option<&str>) -> io::Result<Option<T>> {
+    match value {
+        None | Some("none") => Ok(None),
+        Some(value) => value.parse().map(Some).map_err(|_| invalid(&format!("invalid value {value}"))),
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::topology::{Hexagonal, Neighbourhood, Topology};
+
+    #[test]
+    fn test_save_and_load() {
+        let board = Board::from_text("*...\n..2.\n....\n#...")
+            .unwrap()
+            .with_topology(Hexagonal)
+            .with_edges(Edges::Wrapping);
+        let clock = Arc::new(ManualClock::default());
+        let mut game = Game::new_with_board(board, Difficulty::Medium)
+            .with_rules(Rules {
+                lives: 2,
+                flag_limit: FlagLimit::Count(5),
+                move_limit: Some(20),
+                ..Default::default()
+            })
+            .with_clock(clock.clone());
+        game.board.add_cell_numbers();
+        game.selected_at(Point { x: 3, y: 3 });
+        game.cycle_mark(Point { x: 1, y: 2 });
+        game.add_flag(Point { x: 1, y: 2 });
+        game.cycle_mark(Point { x: 2, y: 0 });
+        game.cycle_mark(Point { x: 2, y: 0 });
+        clock.advance(Duration::from_millis(4250));
+
+        let mut saved = vec![];
+        game.save(&mut saved).unwrap();
+        let loaded = Game::load(saved.as_slice()).unwrap();
+
+        assert_eq!(loaded.board.to_text(), game.board.to_text());
+        assert_eq!(loaded.player_view(), game.player_view());
+        assert_eq!(loaded.board.cells, game.board.cells);
+        assert_eq!(loaded.board.topology.to_text(), Some("hexagonal".to_string()));
+        assert_eq!(loaded.board.edges, Edges::Wrapping);
+        assert_eq!(loaded.board.mines_per_cell, 2);
+        assert_eq!(loaded.difficulty, Difficulty::Medium);
+        assert_eq!(loaded.total_mines, 3);
+        assert_eq!(loaded.statistics, game.statistics);
+        assert_eq!(loaded.rules, game.rules);
+        assert_eq!(loaded.elapsed(), Duration::from_millis(4250));
+    }
+
+    #[test]
+    fn test_resumed_game_keeps_time() {
+        let clock = Arc::new(ManualClock::default());
+        let saved = format!("{HEADER} 1\nelapsed_ms 3000\nsize 2 2\nlayout\n*.\n..\nstate\n--\n--\n");
+        let mut game = Game::load(saved.as_bytes()).unwrap().with_clock(clock.clone());
+        assert_eq!(game.elapsed(), Duration::from_secs(3));
+
+        game.selected_at(Point { x: 1, y: 1 });
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(game.elapsed(), Duration::from_secs(5));
+
+        game.selected_at(Point { x: 0, y: 1 });
+        clock.advance(Duration::from_secs(2));
+        game.toggle_flagged(Point::zero());
+        clock.advance(Duration::from_secs(2));
+        assert!(game.is_win());
+        assert_eq!(game.elapsed(), Duration::from_secs(7));
+        assert_eq!(game.rules, Rules::default());
+    }
+
+    #[test]
+    fn test_elapsed_is_clamped_to_time_limit() {
+        let saved = format!("{HEADER} 1\nelapsed_ms {}\ntime_limit_ms 20000\nsize 1 2\nlayout\n*.\nstate\n--\n", u64::MAX);
+        let game = Game::load(saved.as_bytes()).unwrap();
+
+        assert_eq!(game.elapsed(), Duration::from_secs(20));
+        assert_eq!(game.loss(), Some(crate::rules::Loss::TimeUp));
+    }
+
+    #[test]
+    fn test_topologies() {
+        for topology in [
+            Arc::new(Neighbourhood::knight()) as Arc<dyn Topology>,
+            Arc::new(crate::topology::Cubic { depth: 2 }),
+        ] {
+            let mut game = Game::new_with_board(Board::from_text("*..\n...\n..*\n...").unwrap(), Difficulty::Easy);
+            game.board.topology = topology;
+            game.board.add_cell_numbers();
+
+            let mut saved = vec![];
+            game.save(&mut saved).unwrap();
+            let loaded = Game::load(saved.as_slice()).unwrap();
+
+            assert_eq!(loaded.board.topology.to_text(), game.board.topology.to_text());
+            assert_eq!(loaded.board.cells, game.board.cells);
+        }
+    }
+
+    #[test]
+    fn test_mines_hit_with_lives_left() {
+        let rules = Rules { lives: 3, ..Default::default() };
+        let mut game = Game::new_with_board(Board::from_text("*..\n.2.\n...").unwrap(), Difficulty::Easy).with_rules(rules);
+        game.selected_at(Point::zero());
+        game.selected_at(Point { x: 1, y: 1 });
+        assert_eq!(game.flags_remaining(), 0);
+
+        let mut saved = vec![];
+        game.save(&mut saved).unwrap();
+        let loaded = Game::load(saved.as_slice()).unwrap();
+
+        assert_eq!(loaded.board.cells, game.board.cells);
+        assert_eq!(loaded.flags_remaining(), game.flags_remaining());
+        assert_eq!(loaded.lives_remaining(), game.lives_remaining());
+    }
+
+    #[test]
+    fn test_invalid_saves() {
+        #[derive(Debug)]
+        struct Custom;
+        impl Topology for Custom {
+            fn neighbours(&self, _: Point, _: Size, _: Edges) -> Vec<Point> {
+                vec![]
+            }
+        }
+
+        let game = Game::new_with_board(Board::new_empty(Size { width: 2, height: 2 }).with_topology(Custom), Difficulty::Easy);
+        assert_eq!(game.save(vec![]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+        let mut game = Game::new_with_board(Board::from_text("*.\n..").unwrap(), Difficulty::Easy);
+        game.board.cells.data[0][1].mark = Mark::Flag(0);
+        assert_eq!(game.save(vec![]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        game.board.cells.data[0][1].mark = Mark::None;
+        game.board.cells.data[0][0].mines = 10;
+        assert_eq!(game.save(vec![]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+        let error = Game::load(format!("{HEADER} 1\nedges whatever\nsize 1 1\nlayout\n.\nstate\n-\n").as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "unknown edges whatever");
+        let overflow = format!("{HEADER} 1\ntopology neighbourhood 2147483647,2147483647 -2147483648,-2147483648\nsize 2 2\nlayout\n*.\n..\nstate\n--\n--\n");
+        assert_eq!(Game::load(overflow.as_bytes()).unwrap().board.cell_at(Point { x: 1, y: 1 }).unwrap().number, 0);
+
+        let error = Game::load(format!("{HEADER} 1\ndifficulty expert\nsize 1 1\nlayout\n.\nstate\n-\n").as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "unknown difficulty expert");
+        assert!(Game::load(format!("{HEADER} 1\nsize 1 1\nlayout\n.\nstate\nF\n").as_bytes()).is_err());
+        let hexagonal = format!("{HEADER} 1\ntopology hexagonal\nedges wrapping\nsize 3 3\nlayout\n*..\n...\n...\nstate\n---\n---\n---\n");
+        assert!(Game::load(hexagonal.as_bytes()).is_err());
+        assert!(Game::load(hexagonal.replace("edges wrapping", "edges bounded").as_bytes()).is_ok());
+        assert!(Game::load(format!("{HEADER} 1\nmines_per_cell 12\nsize 1 1\nlayout\n.\nstate\n-\n").as_bytes()).is_err());
+
+        let error = Game::load(format!("{HEADER} 2\n").as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "unsupported save version 2");
+        assert!(Game::load("hello".as_bytes()).is_err());
+        assert!(Game::load(format!("{HEADER} 1\nsize 2 2\nlayout\n*.\n..\nstate\n-\n--\n").as_bytes()).is_err());
+    }
+}
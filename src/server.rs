@@ -0,0 +1,228 @@
+//! Game server speaking JSON lines over TCP.
+//!
+//! Every message is a single JSON object followed by a newline. Clients send
+//! commands, tagged by `command`:
+//!
+//! ```text
+//! {"command":"create","difficulty":"easy"}
+//! {"command":"subscribe","game":1}
+//! {"command":"reveal","game":1,"x":0,"y":3}
+//! {"command":"flag","game":1,"x":0,"y":3}
+//! {"command":"chord","game":1,"x":0,"y":3}
+//! ```
+//!
+//! The server answers with events, tagged by `event`:
+//!
+//! - `created`: the id of a new game. The creator is subscribed to it.
+//! - `state`: the whole player view of a game, sent when subscribing.
+//! - `delta`: the cells changed by one action, sent to every subscriber.
+//! - `error`: the command could not be handled.
+//!
+//! Only `PlayerView` cells are ever sent, so clients never learn where the
+//! hidden mines are.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{Difficulty, Game};
+use crate::graphics::Point;
+use crate::session::{Action, Delta, PlayerId, Session};
+use crate::view::PlayerView;
+
+pub type GameId = u64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    Create { difficulty: Difficulty },
+    Subscribe { game: GameId },
+    Reveal { game: GameId, x: usize, y: usize },
+    Flag { game: GameId, x: usize, y: usize },
+    Chord { game: GameId, x: usize, y: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    Created { game: GameId },
+    State { game: GameId, view: PlayerView, status: Status },
+    Delta { game: GameId, delta: Delta, status: Status },
+    Error { message: String },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Playing,
+    Won,
+    Lost,
+}
+
+impl From<&Game> for Status {
+    fn from(game: &Game) -> Self {
+        if game.is_game_over() {
+            Status::Lost
+        } else if game.is_win() {
+            Status::Won
+        } else {
+            Status::Playing
+        }
+    }
+}
+
+struct Hosted {
+    session: Session,
+    subscribers: Vec<Sender<String>>,
+}
+
+/// Games shared by every connection of a server.
+#[derive(Default)]
+pub struct Server {
+    games: Mutex<HashMap<GameId, Hosted>>,
+    next_game: Mutex<GameId>,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Server::default()
+    }
+
+    /// Accepts connections until the listener fails, one thread per client.
+    pub fn serve(self: Arc<Self>, listener: TcpListener) -> io::Result<()> {
+        for (player, stream) in listener.incoming().enumerate() {
+            let server = Arc::clone(&self);
+            let stream = stream?;
+            thread::spawn(move || server.handle_connection(stream, player as PlayerId));
+        }
+        Ok(())
+    }
+
+    fn handle_connection(&self, stream: TcpStream, player: PlayerId) -> io::Result<()> {
+        let (sender, receiver) = mpsc::channel::<String>();
+        let mut writer = stream.try_clone()?;
+        thread::spawn(move || {
+            for line in receiver {
+                if writeln!(writer, "{line}").is_err() {
+                    break;
+                }
+            }
+        });
+
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Command>(&line) {
+                Ok(command) => self.handle(command, player, &sender),
+                Err(error) => send(&sender, &Event::Error { message: error.to_string() }),
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs a command for a player, replying through `sender`.
+    pub fn handle(&self, command: Command, player: PlayerId, sender: &Sender<String>) {
+        let mut games = self.games.lock().unwrap();
+        let (game, action) = match command {
+            Command::Create { difficulty } => {
+                let game = {
+                    let mut next_game = self.next_game.lock().unwrap();
+                    *next_game += 1;
+                    *next_game
+                };
+                let session = Session::new(Game::new(difficulty));
+                let state = state(game, &session);
+                games.insert(
+                    game,
+                    Hosted {
+                        session,
+                        subscribers: vec![sender.clone()],
+                    },
+                );
+                send(sender, &Event::Created { game });
+                send(sender, &state);
+                return;
+            }
+            Command::Subscribe { game } => {
+                let Some(hosted) = games.get_mut(&game) else {
+                    return send(sender, &unknown_game(game));
+                };
+                send(sender, &state(game, &hosted.session));
+                hosted.subscribers.push(sender.clone());
+                return;
+            }
+            Command::Reveal { game, x, y } => (game, Action::Reveal(Point { x, y })),
+            Command::Flag { game, x, y } => (game, Action::Flag(Point { x, y })),
+            Command::Chord { game, x, y } => (game, Action::Chord(Point { x, y })),
+        };
+
+        let Some(Hosted { session, subscribers }) = games.get_mut(&game) else {
+            return send(sender, &unknown_game(game));
+        };
+        session.submit(player, action);
+        for delta in session.tick() {
+            let event = Event::Delta {
+                game,
+                delta,
+                status: Status::from(&session.game),
+            };
+            let line = serde_json::to_string(&event).unwrap();
+            subscribers.retain(|subscriber| subscriber.send(line.clone()).is_ok());
+        }
+    }
+}
+
+fn state(game: GameId, session: &Session) -> Event {
+    Event::State {
+        game,
+        view: session.game.player_view(),
+        status: Status::from(&session.game),
+    }
+}
+
+fn unknown_game(game: GameId) -> Event {
+    Event::Error {
+        message: format!("unknown game {game}"),
+    }
+}
+
+fn send(sender: &Sender<String>, event: &Event) {
+    _ = sender.send(serde_json::to_string(event).unwrap());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_format() {
+        let command: Command = serde_json::from_str(r#"{"command":"reveal","game":1,"x":2,"y":3}"#).unwrap();
+        assert_eq!(command, Command::Reveal { game: 1, x: 2, y: 3 });
+
+        let command: Command = serde_json::from_str(r#"{"command":"create","difficulty":"hard"}"#).unwrap();
+        assert!(matches!(command, Command::Create { difficulty: Difficulty::Hard }));
+    }
+
+    #[test]
+    fn test_unknown_game() {
+        let server = Server::new();
+        let (sender, receiver) = mpsc::channel();
+
+        server.handle(Command::Flag { game: 4, x: 0, y: 0 }, 0, &sender);
+
+        let event: Event = serde_json::from_str(&receiver.recv().unwrap()).unwrap();
+        assert_eq!(
+            event,
+            Event::Error {
+                message: "unknown game 4".to_string()
+            }
+        );
+    }
+}
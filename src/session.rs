@@ -0,0 +1,220 @@
+use crate::game::Game;
+use crate::graphics::*;
+use crate::view::VisibleCell;
+
+pub type PlayerId = u32;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    Reveal(Point),
+    Flag(Point),
+    Chord(Point),
+}
+
+impl Action {
+    pub fn coordinates(&self) -> Point {
+        match self {
+            Action::Reveal(coordinates) | Action::Flag(coordinates) | Action::Chord(coordinates) => *coordinates,
+        }
+    }
+
+    /// Flags are applied before reveals in the same tick, so a reveal
+    /// racing a flag on the same cell never explodes it.
+    fn phase(&self) -> u8 {
+        match self {
+            Action::Flag(_) => 0,
+            Action::Reveal(_) | Action::Chord(_) => 1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outcome {
+    Applied,
+    /// Nothing changed, for example revealing a cleared cell.
+    Ignored,
+    /// Another player acted on the same cell earlier in the tick.
+    Conflict,
+    /// The game was already won or lost.
+    GameOver,
+}
+
+/// Result of one action, with the cells it changed as seen by players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Delta {
+    pub tick: u64,
+    pub player: PlayerId,
+    pub action: Action,
+    pub outcome: Outcome,
+    pub changes: Vec<(Point, VisibleCell)>,
+}
+
+/// Game shared by several players. Actions are queued with `submit` and
+/// applied together by `tick`, sorted by phase (flags first), then player
+/// id, then submission order.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub game: Game,
+    tick: u64,
+    pending: Vec<(PlayerId, Action)>,
+    owners: Vec2<Option<PlayerId>>,
+}
+
+impl Session {
+    pub fn new(game: Game) -> Self {
+        let owners = game.board.cells.map(|_| None);
+        Session {
+            game,
+            tick: 0,
+            pending: vec![],
+            owners,
+        }
+    }
+
+    pub fn submit(&mut self, player: PlayerId, action: Action) {
+        self.pending.push((player, action));
+    }
+
+    pub fn current_tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Player that revealed or flagged the cell.
+    pub fn owner_at(&self, coordinates: Point) -> Option<PlayerId> {
+        self.owners.get_element(coordinates).copied().flatten()
+    }
+
+    pub fn tick(&mut self) -> Vec<Delta> {
+        let mut actions = std::mem::take(&mut self.pending);
+        actions.sort_by_key(|(player, action)| (action.phase(), *player));
+
+        let mut touched: Vec<Point> = vec![];
+        let deltas = actions
+            .into_iter()
+            .map(|(player, action)| {
+                let coordinates = action.coordinates();
+                let conflict = touched.contains(&coordinates);
+                touched.push(coordinates);
+
+                let (outcome, changes) = if conflict { (Outcome::Conflict, vec![]) } else { self.apply(player, action) };
+                Delta {
+                    tick: self.tick,
+                    player,
+                    action,
+                    outcome,
+                    changes,
+                }
+            })
+            .collect();
+
+        self.tick += 1;
+        deltas
+    }
+
+    fn apply(&mut self, player: PlayerId, action: Action) -> (Outcome, Vec<(Point, VisibleCell)>) {
+        if self.game.is_game_over() || self.game.is_win() {
+            return (Outcome::GameOver, vec![]);
+        }
+
+        let before = self.game.player_view();
+        match action {
+            Action::Reveal(coordinates) => {
+                if self.game.board.cell_at(coordinates).is_some_and(|cell| !cell.is_flagged()) {
+                    self.game.selected_at(coordinates);
+                }
+            }
+            Action::Flag(coordinates) => _ = self.game.toggle_flagged(coordinates),
+            Action::Chord(coordinates) => _ = self.game.chord_at(coordinates),
+        }
+        let changes = before.diff(&self.game.player_view());
+
+        changes.iter().for_each(|(point, cell)| {
+            let owner = if *cell == VisibleCell::Hidden { None } else { Some(player) };
+            self.owners.replace_at(owner, *point);
+        });
+        let outcome = if changes.is_empty() { Outcome::Ignored } else { Outcome::Applied };
+        (outcome, changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::game::Difficulty;
+
+    fn session() -> Session {
+        let board = Board::from_text("*...\n....\n....\n...*").unwrap();
+        Session::new(Game::new_with_board(board, Difficulty::Easy))
+    }
+
+    #[test]
+    fn test_flag_wins_over_reveal() {
+        for reveal_first in [true, false] {
+            let mut session = session();
+            if reveal_first {
+                session.submit(1, Action::Reveal(Point::zero()));
+                session.submit(2, Action::Flag(Point::zero()));
+            } else {
+                session.submit(2, Action::Flag(Point::zero()));
+                session.submit(1, Action::Reveal(Point::zero()));
+            }
+
+            let deltas = session.tick();
+
+            assert_eq!(deltas[0].player, 2);
+            assert_eq!(deltas[0].outcome, Outcome::Applied);
+            assert_eq!(deltas[0].changes, vec![(Point::zero(), VisibleCell::Flagged(1))]);
+            assert_eq!(deltas[1].player, 1);
+            assert_eq!(deltas[1].outcome, Outcome::Conflict);
+            assert!(!session.game.is_game_over());
+            assert_eq!(session.owner_at(Point::zero()), Some(2));
+        }
+    }
+
+    #[test]
+    fn test_same_cell_flags() {
+        let mut session = session();
+        session.submit(7, Action::Flag(Point { x: 3, y: 3 }));
+        session.submit(3, Action::Flag(Point { x: 3, y: 3 }));
+
+        let deltas = session.tick();
+
+        assert_eq!((deltas[0].player, deltas[0].outcome), (3, Outcome::Applied));
+        assert_eq!((deltas[1].player, deltas[1].outcome), (7, Outcome::Conflict));
+        assert!(session.game.board.cell_at(Point { x: 3, y: 3 }).unwrap().is_flagged());
+        assert_eq!(session.current_tick(), 1);
+    }
+
+    #[test]
+    fn test_reveal_deltas_and_attribution() {
+        let mut session = session();
+        session.submit(2, Action::Reveal(Point { x: 1, y: 0 }));
+        session.submit(2, Action::Reveal(Point { x: 1, y: 0 }));
+        session.submit(1, Action::Reveal(Point { x: 0, y: 3 }));
+
+        let deltas = session.tick();
+
+        assert_eq!(deltas[0].player, 1);
+        assert_eq!(deltas[0].changes.len(), 14);
+        assert!(deltas[0].changes.contains(&(Point { x: 1, y: 0 }, VisibleCell::Revealed(1))));
+        assert_eq!(session.owner_at(Point { x: 1, y: 0 }), Some(1));
+        assert_eq!(session.owner_at(Point::zero()), None);
+        assert_eq!(deltas[1].outcome, Outcome::Ignored);
+        assert_eq!(deltas[2].outcome, Outcome::Conflict);
+
+        session.submit(2, Action::Flag(Point { x: 3, y: 3 }));
+        session.submit(1, Action::Flag(Point::zero()));
+        session.tick();
+        assert!(session.game.is_win());
+
+        session.submit(1, Action::Flag(Point::zero()));
+        let deltas = session.tick();
+
+        assert_eq!(deltas[0].tick, 2);
+        assert_eq!(deltas[0].outcome, Outcome::GameOver);
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Statistics {
+    pub left_clicks: u32,
+    pub right_clicks: u32,
+    pub chords: u32,
+    pub wasted_clicks: u32,
+}
+
+impl Statistics {
+    pub fn clicks(&self) -> u32 {
+        self.left_clicks + self.right_clicks + self.chords
+    }
+
+    pub fn effective_clicks(&self) -> u32 {
+        self.clicks() - self.wasted_clicks
+    }
+
+    pub fn efficiency(&self, bbbv: u32, elapsed: Duration) -> Efficiency {
+        let ratio = |clicks: u32| {
+            if clicks == 0 {
+                return 0.0;
+            }
+            bbbv as f64 / clicks as f64
+        };
+        let seconds = elapsed.as_secs_f64();
+
+        Efficiency {
+            bbbv,
+            bbbv_per_second: if seconds > 0.0 { bbbv as f64 / seconds } else { 0.0 },
+            ioe: ratio(self.clicks()),
+            throughput: ratio(self.effective_clicks()),
+        }
+    }
+}
+
+/// End of game metrics, as reported by Minesweeper Arbiter.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Efficiency {
+    pub bbbv: u32,
+    pub bbbv_per_second: f64,
+    /// 3BV per click.
+    pub ioe: f64,
+    /// 3BV per effective (non wasted) click.
+    pub throughput: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_efficiency() {
+        let statistics = Statistics {
+            left_clicks: 8,
+            right_clicks: 3,
+            chords: 1,
+            wasted_clicks: 2,
+        };
+        let efficiency = statistics.efficiency(6, Duration::from_secs(4));
+
+        assert_eq!(efficiency.bbbv, 6);
+        assert_eq!(efficiency.bbbv_per_second, 1.5);
+        assert_eq!(efficiency.ioe, 0.5);
+        assert_eq!(efficiency.throughput, 0.6);
+    }
+
+    #[test]
+    fn test_efficiency_without_clicks() {
+        let efficiency = Statistics::default().efficiency(6, Duration::ZERO);
+
+        assert_eq!(efficiency.bbbv_per_second, 0.0);
+        assert_eq!(efficiency.ioe, 0.0);
+        assert_eq!(efficiency.throughput, 0.0);
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt::Write;
+
+use crate::board::Board;
+use crate::cell::Cell;
+
+const BORDER: &str = "#000000";
+const HIDDEN: &str = "#808080";
+const REVEALED: &str = "#ffffff";
+const EXPLODED: &str = "#ff0000";
+const FONT_FAMILY: &str = "Fira Sans";
+
+/// Draws boards as SVG documents, styled like `examples/visual.rs`. Cells
+/// are written in row order with integer coordinates, so the same board
+/// always gives the same document.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SvgRenderer {
+    /// Side of each cell, in pixels.
+    pub cell_size: u32,
+    /// Draws the hidden mines too, like at the end of a lost game.
+    pub reveal_mines: bool,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer {
+            cell_size: 40,
+            reveal_mines: false,
+        }
+    }
+}
+
+impl SvgRenderer {
+    pub fn new() -> Self {
+        SvgRenderer::default()
+    }
+
+    pub fn with_cell_size(self, cell_size: u32) -> Self {
+        SvgRenderer { cell_size, ..self }
+    }
+
+    pub fn with_reveal_mines(self, reveal_mines: bool) -> Self {
+        SvgRenderer { reveal_mines, ..self }
+    }
+
+    pub fn render(&self, board: &Board) -> String {
+        let size = self.cell_size;
+        let width = board.get_height() as u32 * size;
+        let height = board.get_width() as u32 * size;
+
+        let mut svg = String::new();
+        _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        board.for_each_cell(|_, cell, _| self.draw_cell(&mut svg, cell));
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Rasterizes the SVG document, with numbers in the bundled Fira Sans.
+    #[cfg(feature = "png")]
+    pub fn render_png(&self, board: &Board) -> Option<Vec<u8>> {
+        use resvg::{tiny_skia, usvg};
+
+        const FONT: &[u8] = include_bytes!("../examples/assets/FiraSans-Medium.ttf");
+
+        let mut options = usvg::Options {
+            font_family: FONT_FAMILY.to_string(),
+            ..Default::default()
+        };
+        options.fontdb_mut().load_font_data(FONT.to_vec());
+        let tree = usvg::Tree::from_str(&self.render(board), &options).ok()?;
+
+        let size = tree.size().to_int_size();
+        let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())?;
+        resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+        pixmap.encode_png().ok()
+    }
+
+    fn draw_cell(&self, svg: &mut String, cell: &Cell) {
+        let size = self.cell_size;
+        let x = cell.coordinates.y as u32 * size;
+        let y = cell.coordinates.x as u32 * size;
+
+        let fill = match (cell.cleared, cell.is_mine()) {
+            (true, true) => EXPLODED,
+            (true, false) => REVEALED,
+            (false, _) => HIDDEN,
+        };
+        _ = writeln!(svg, r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" fill="{BORDER}"/>"#);
+        _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{fill}"/>"#,
+            x + 1,
+            y + 1,
+            size.saturating_sub(2),
+            size.saturating_sub(2)
+        );
+
+        if cell.is_flagged() && !cell.cleared {
+            self.draw_flag(svg, x, y);
+        } else if cell.is_mine() && (cell.cleared || self.reveal_mines) {
+            self.draw_mine(svg, x, y);
+        } else if cell.cleared && cell.number > 0 {
+            _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="{FONT_FAMILY}" font-size="{}" text-anchor="middle" fill="{BORDER}">{}</text>"#,
+                x + size / 2,
+                y + size * 27 / 40,
+                size / 2,
+                cell.number
+            );
+        }
+    }
+
+    fn draw_flag(&self, svg: &mut String, x: u32, y: u32) {
+        // Lengths in twentieths of a cell, at least a pixel wide on small cells.
+        let unit = |twentieths: u32| (self.cell_size * twentieths / 20).max(1);
+        let pole = x + unit(8);
+        _ = writeln!(svg, r#"<rect x="{pole}" y="{}" width="{}" height="{}" fill="{BORDER}"/>"#, y + unit(4), unit(1), unit(12));
+        _ = writeln!(svg, r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{BORDER}"/>"#, x + unit(5), y + unit(15), unit(8), unit(1));
+        _ = writeln!(
+            svg,
+            r#"<polygon points="{},{} {},{} {},{}" fill="{EXPLODED}"/>"#,
+            pole + unit(1),
+            y + unit(4),
+            pole + unit(7),
+            y + unit(7),
+            pole + unit(1),
+            y + unit(10)
+        );
+    }
+
+    fn draw_mine(&self, svg: &mut String, x: u32, y: u32) {
+        let size = self.cell_size;
+        let (center_x, center_y) = (x + size / 2, y + size / 2);
+        _ = writeln!(
+            svg,
+            r#"<path d="M{} {center_y}H{}M{center_x} {}V{}" stroke="{BORDER}" stroke-width="{}"/>"#,
+            x + size / 5,
+            x + size * 4 / 5,
+            y + size / 5,
+            y + size * 4 / 5,
+            (size / 20).max(1)
+        );
+        _ = writeln!(svg, r#"<circle cx="{center_x}" cy="{center_y}" r="{}" fill="{BORDER}"/>"#, size / 4);
+    }
+}
+
+impl Board {
+    /// SVG document of the board with the default renderer.
+    pub fn to_svg(&self) -> String {
+        SvgRenderer::new().render(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Difficulty, Game};
+    use crate::graphics::Point;
+
+    #[test]
+    fn test_svg_snapshot() {
+        let board = Board::from_text("*.\n#.").unwrap();
+        let mut game = Game::new_with_board(board.clone(), Difficulty::Easy);
+        game.selected_at(Point { x: 0, y: 1 });
+        game.selected_at(Point::zero());
+
+        let svg = SvgRenderer::new().with_cell_size(20).render(&game.board);
+
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="40" viewBox="0 0 40 40">
+<rect x="0" y="0" width="20" height="20" fill="#000000"/>
+<rect x="1" y="1" width="18" height="18" fill="#ff0000"/>
+<path d="M4 10H16M10 4V16" stroke="#000000" stroke-width="1"/>
+<circle cx="10" cy="10" r="5" fill="#000000"/>
+<rect x="20" y="0" width="20" height="20" fill="#000000"/>
+<rect x="21" y="1" width="18" height="18" fill="#ffffff"/>
+<text x="30" y="13" font-family="Fira Sans" font-size="10" text-anchor="middle" fill="#000000">1</text>
+<rect x="20" y="20" width="20" height="20" fill="#000000"/>
+<rect x="21" y="21" width="18" height="18" fill="#808080"/>
+</svg>
+"##
+        );
+        assert!(game.board.to_svg().starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="80" height="80" viewBox="0 0 80 80">"#));
+        assert_ne!(game.board.to_svg(), board.to_svg());
+    }
+
+    #[test]
+    fn test_svg_small_flags() {
+        let mut board = Board::from_text("*").unwrap();
+        board.cells.data[0][0].mark = crate::cell::Mark::Flag(1);
+
+        let svg = SvgRenderer::new().with_cell_size(10).render(&board);
+        assert!(svg.contains(r##"<rect x="4" y="2" width="1" height="6" fill="#000000"/>"##));
+        assert!(svg.contains(r##"<polygon points="5,2 7,3 5,5" fill="#ff0000"/>"##));
+    }
+
+    #[test]
+    fn test_svg_hidden_mines() {
+        let mut board = Board::from_text("*.\n.*").unwrap();
+        board.cells.data[1][1].mark = crate::cell::Mark::Flag(1);
+
+        assert!(!board.to_svg().contains("<circle"));
+        assert_eq!(board.to_svg().matches("<polygon").count(), 1);
+
+        let revealed = SvgRenderer::new().with_reveal_mines(true).render(&board);
+        assert_eq!(revealed.matches("<circle").count(), 1);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn test_png() {
+        let board = Board::from_text("*.\n..").unwrap();
+        let png = SvgRenderer::new().render_png(&board).unwrap();
+
+        assert_eq!(&png[1..4], b"PNG");
+        assert_eq!(SvgRenderer::new().render_png(&board).unwrap(), png);
+    }
+}
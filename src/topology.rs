@@ -0,0 +1,381 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::graphics::*;
+
+/// Defines which cells are adjacent to each other on a board.
+pub trait Topology: fmt::Debug + Send + Sync {
+    fn neighbours(&self, coordinates: Point, size: Size, edges: Edges) -> Vec<Point>;
+
+    /// Description read back by `topology_from_text`, used by saved games.
+    /// Topologies that can not be saved return `None`.
+    fn to_text(&self) -> Option<String> {
+        None
+    }
+
+    /// Whether boards of this size and edges can use the topology.
+    fn supports(&self, _size: Size, _edges: Edges) -> bool {
+        true
+    }
+}
+
+pub(crate) fn topology_from_text(text: &str) -> Option<Arc<dyn Topology>> {
+    let mut words = text.split_whitespace();
+    let topology: Arc<dyn Topology> = match words.next()? {
+        "square" => Arc::new(Square),
+        "hexagonal" => Arc::new(Hexagonal),
+        "cubic" => Arc::new(Cubic {
+            depth: words.next()?.parse().ok()?,
+        }),
+        "neighbourhood" => {
+            let offsets = words
+                .map(|offset| {
+                    let (dx, dy) = offset.split_once(',')?;
+                    Some((dx.parse().ok()?, dy.parse().ok()?))
+                })
+                .collect::<Option<Vec<(i32, i32)>>>()?;
+            Arc::new(Neighbourhood { offsets })
+        }
+        _ => return None,
+    };
+    Some(topology)
+}
+
+/// What happens to neighbours that fall outside of the board.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Edges {
+    /// Cells on the edges have fewer neighbours.
+    #[default]
+    Bounded,
+    /// Edges wrap around, turning the board into a torus.
+    Wrapping,
+}
+
+/// Classic grid where every cell touches the eight cells around it.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Square;
+
+impl Topology for Square {
+    fn to_text(&self) -> Option<String> {
+        Some("square".to_string())
+    }
+
+    fn neighbours(&self, coordinates: Point, size: Size, edges: Edges) -> Vec<Point> {
+        let mut points = vec![];
+        for dx in [-1, 0, 1] {
+            for dy in [-1, 0, 1] {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                if let Some(point) = offset(coordinates, dx, dy, size, edges) {
+                    points.push(point);
+                }
+            }
+        }
+        unique_neighbours(coordinates, points)
+    }
+}
+
+/// Grid of hexagons with six neighbours each, stored in "odd-r" offset
+/// coordinates: every row (`x`) with an odd index is shifted half a cell.
+/// Wrapping boards need an even width to tile seamlessly.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Hexagonal;
+
+impl Hexagonal {
+    const DIRECTIONS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+}
+
+impl Topology for Hexagonal {
+    fn to_text(&self) -> Option<String> {
+        Some("hexagonal".to_string())
+    }
+
+    fn supports(&self, size: Size, edges: Edges) -> bool {
+        edges == Edges::Bounded || size.width.is_multiple_of(2)
+    }
+
+    fn neighbours(&self, coordinates: Point, size: Size, edges: Edges) -> Vec<Point> {
+        let hex = Hex::from_offset(coordinates);
+        let points = Hexagonal::DIRECTIONS
+            .iter()
+            .filter_map(|(dq, dr)| Hex { q: hex.q + dq, r: hex.r + dr }.to_offset(size, edges))
+            .collect();
+        unique_neighbours(coordinates, points)
+    }
+}
+
+/// Square grid where adjacency is given by a set of relative offsets,
+/// for variants like knight's move or orthogonal only counting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighbourhood {
+    pub offsets: Vec<(i32, i32)>,
+}
+
+impl Neighbourhood {
+    pub fn new(offsets: Vec<(i32, i32)>) -> Self {
+        Neighbourhood { offsets }
+    }
+
+    /// Every cell at most `radius` cells away in both axes.
+    pub fn radius(radius: i32) -> Self {
+        let mut offsets = vec![];
+        for dx in -radius..=radius {
+            for dy in -radius..=radius {
+                if dx != 0 || dy != 0 {
+                    offsets.push((dx, dy));
+                }
+            }
+        }
+        Neighbourhood { offsets }
+    }
+
+    /// Orthogonal neighbours only.
+    pub fn cross() -> Self {
+        Neighbourhood {
+            offsets: vec![(-1, 0), (1, 0), (0, -1), (0, 1)],
+        }
+    }
+
+    /// Cells a chess knight can jump to.
+    pub fn knight() -> Self {
+        Neighbourhood {
+            offsets: vec![(-2, -1), (-2, 1), (-1, -2), (-1, 2), (1, -2), (1, 2), (2, -1), (2, 1)],
+        }
+    }
+}
+
+impl Topology for Neighbourhood {
+    fn to_text(&self) -> Option<String> {
+        let offsets: Vec<String> = self.offsets.iter().map(|(dx, dy)| format!("{dx},{dy}")).collect();
+        Some(format!("neighbourhood {}", offsets.join(" ")))
+    }
+
+    fn neighbours(&self, coordinates: Point, size: Size, edges: Edges) -> Vec<Point> {
+        let points = self.offsets.iter().filter_map(|(dx, dy)| offset(coordinates, *dx, *dy, size, edges)).collect();
+        unique_neighbours(coordinates, points)
+    }
+}
+
+/// Three dimensional grid, where every cell touches up to 26 others.
+/// The board stores the `depth` layers one after the other along `x`,
+/// see `Point3::flatten`.
+#[derive(Debug, Copy, Clone)]
+pub struct Cubic {
+    pub depth: usize,
+}
+
+impl Topology for Cubic {
+    fn to_text(&self) -> Option<String> {
+        Some(format!("cubic {}", self.depth))
+    }
+
+    fn neighbours(&self, coordinates: Point, size: Size, edges: Edges) -> Vec<Point> {
+        if self.depth == 0 || size.width < self.depth {
+            return vec![];
+        }
+        let size = Size3 {
+            width: size.width / self.depth,
+            height: size.height,
+            depth: self.depth,
+        };
+        let point = Point3::unflatten(coordinates, size);
+
+        let mut points = vec![];
+        for dx in [-1, 0, 1] {
+            for dy in [-1, 0, 1] {
+                for dz in [-1, 0, 1] {
+                    if dx == 0 && dy == 0 && dz == 0 {
+                        continue;
+                    }
+                    let x = wrap(point.x as i32 + dx, size.width, edges);
+                    let y = wrap(point.y as i32 + dy, size.height, edges);
+                    let z = wrap(point.z as i32 + dz, size.depth, edges);
+                    if let (Some(x), Some(y), Some(z)) = (x, y, z) {
+                        points.push(Point3 { x, y, z }.flatten(size));
+                    }
+                }
+            }
+        }
+        unique_neighbours(coordinates, points)
+    }
+}
+
+/// Axial coordinates of a hexagon, where `r` is the row and `q` the
+/// position along it, skewed so that neighbours are constant offsets.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub fn from_offset(coordinates: Point) -> Hex {
+        let (x, y) = (coordinates.x as i32, coordinates.y as i32);
+        Hex { q: y - (x - (x & 1)) / 2, r: x }
+    }
+
+    pub fn to_offset(&self, size: Size, edges: Edges) -> Option<Point> {
+        let x = self.r;
+        let y = self.q + (self.r - (self.r & 1)) / 2;
+        offset(Point::zero(), x, y, size, edges)
+    }
+
+    pub fn distance(&self, other: Hex) -> i32 {
+        let (dq, dr) = (self.q - other.q, self.r - other.r);
+        (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+    }
+}
+
+fn offset(coordinates: Point, dx: i32, dy: i32, size: Size, edges: Edges) -> Option<Point> {
+    // Offsets come from saved games and shared codes, so they can be huge.
+    Some(Point {
+        x: wrap((coordinates.x as i32).checked_add(dx)?, size.width, edges)?,
+        y: wrap((coordinates.y as i32).checked_add(dy)?, size.height, edges)?,
+    })
+}
+
+/// Brings a coordinate back into `0..length`, if the edges allow it.
+fn wrap(value: i32, length: usize, edges: Edges) -> Option<usize> {
+    let length = length as i32;
+    let value = match edges {
+        Edges::Wrapping if length > 0 => value.rem_euclid(length),
+        _ => value,
+    };
+    if value < 0 || value >= length {
+        return None;
+    }
+    Some(value as usize)
+}
+
+/// On small wrapping boards several offsets can land on the same cell,
+/// or on the cell itself.
+fn unique_neighbours(coordinates: Point, points: Vec<Point>) -> Vec<Point> {
+    let mut unique: Vec<Point> = vec![];
+    points.into_iter().for_each(|point| {
+        if point != coordinates && !unique.contains(&point) {
+            unique.push(point);
+        }
+    });
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: Size = Size { width: 4, height: 4 };
+
+    #[test]
+    fn test_square_neighbours() {
+        assert_eq!(Square.neighbours(Point::zero(), SIZE, Edges::Bounded).len(), 3);
+        assert_eq!(Square.neighbours(Point { x: 0, y: 1 }, SIZE, Edges::Bounded).len(), 5);
+        assert_eq!(Square.neighbours(Point { x: 1, y: 1 }, SIZE, Edges::Bounded).len(), 8);
+    }
+
+    #[test]
+    fn test_hexagonal_neighbours() {
+        let mut neighbours = Hexagonal.neighbours(Point { x: 1, y: 1 }, SIZE, Edges::Bounded);
+        neighbours.sort_by_key(|point| (point.x, point.y));
+        assert_eq!(
+            neighbours,
+            vec![
+                Point { x: 0, y: 1 },
+                Point { x: 0, y: 2 },
+                Point { x: 1, y: 0 },
+                Point { x: 1, y: 2 },
+                Point { x: 2, y: 1 },
+                Point { x: 2, y: 2 },
+            ]
+        );
+
+        let mut neighbours = Hexagonal.neighbours(Point { x: 2, y: 1 }, SIZE, Edges::Bounded);
+        neighbours.sort_by_key(|point| (point.x, point.y));
+        assert_eq!(
+            neighbours,
+            vec![
+                Point { x: 1, y: 0 },
+                Point { x: 1, y: 1 },
+                Point { x: 2, y: 0 },
+                Point { x: 2, y: 2 },
+                Point { x: 3, y: 0 },
+                Point { x: 3, y: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hexagonal_edge_neighbours() {
+        assert_eq!(Hexagonal.neighbours(Point::zero(), SIZE, Edges::Bounded).len(), 2);
+        assert_eq!(Hexagonal.neighbours(Point { x: 1, y: 3 }, SIZE, Edges::Bounded).len(), 3);
+        assert_eq!(Hexagonal.neighbours(Point { x: 1, y: 0 }, SIZE, Edges::Bounded).len(), 5);
+        assert_eq!(Hexagonal.neighbours(Point { x: 3, y: 3 }, SIZE, Edges::Bounded).len(), 2);
+    }
+
+    #[test]
+    fn test_wrapping_neighbours() {
+        for x in 0..SIZE.width {
+            for y in 0..SIZE.height {
+                let point = Point { x, y };
+                assert_eq!(Square.neighbours(point, SIZE, Edges::Wrapping).len(), 8);
+                assert_eq!(Hexagonal.neighbours(point, SIZE, Edges::Wrapping).len(), 6);
+            }
+        }
+
+        let neighbours = Square.neighbours(Point::zero(), SIZE, Edges::Wrapping);
+        assert!(neighbours.contains(&Point { x: 3, y: 3 }));
+        assert!(neighbours.contains(&Point { x: 0, y: 3 }));
+        assert!(neighbours.contains(&Point { x: 3, y: 1 }));
+
+        let small = Size { width: 2, height: 2 };
+        assert_eq!(Square.neighbours(Point::zero(), small, Edges::Wrapping).len(), 3);
+    }
+
+    #[test]
+    fn test_neighbourhood() {
+        let center = Point { x: 2, y: 2 };
+        let size = Size { width: 5, height: 5 };
+
+        assert_eq!(Neighbourhood::radius(1).neighbours(center, size, Edges::Bounded).len(), 8);
+        assert_eq!(Neighbourhood::radius(2).neighbours(center, size, Edges::Bounded).len(), 24);
+        assert_eq!(Neighbourhood::cross().neighbours(center, size, Edges::Bounded).len(), 4);
+        assert_eq!(Neighbourhood::knight().neighbours(center, size, Edges::Bounded).len(), 8);
+
+        let mut corner = Neighbourhood::knight().neighbours(Point::zero(), size, Edges::Bounded);
+        corner.sort_by_key(|point| (point.x, point.y));
+        assert_eq!(corner, vec![Point { x: 1, y: 2 }, Point { x: 2, y: 1 }]);
+    }
+
+    #[test]
+    fn test_cubic_neighbours() {
+        let size = Size3 { width: 3, height: 3, depth: 3 };
+        let cubic = Cubic { depth: 3 };
+        let neighbours = |point: Point3, edges| cubic.neighbours(point.flatten(size), size.flatten(), edges);
+
+        assert_eq!(neighbours(Point3 { x: 1, y: 1, z: 1 }, Edges::Bounded).len(), 26);
+        assert_eq!(neighbours(Point3 { x: 0, y: 0, z: 0 }, Edges::Bounded).len(), 7);
+        assert_eq!(neighbours(Point3 { x: 1, y: 0, z: 0 }, Edges::Bounded).len(), 11);
+        assert_eq!(neighbours(Point3 { x: 1, y: 1, z: 0 }, Edges::Bounded).len(), 17);
+        assert_eq!(neighbours(Point3 { x: 0, y: 0, z: 0 }, Edges::Wrapping).len(), 26);
+
+        let corner = neighbours(Point3 { x: 2, y: 2, z: 2 }, Edges::Bounded);
+        assert!(corner.contains(&Point3 { x: 1, y: 1, z: 1 }.flatten(size)));
+        assert!(!corner.contains(&Point3 { x: 0, y: 2, z: 2 }.flatten(size)));
+    }
+
+    #[test]
+    fn test_hex_coordinates() {
+        for x in 0..SIZE.width {
+            for y in 0..SIZE.height {
+                let point = Point { x, y };
+                assert_eq!(Hex::from_offset(point).to_offset(SIZE, Edges::Bounded), Some(point));
+            }
+        }
+        assert_eq!(Hex { q: -1, r: 0 }.to_offset(SIZE, Edges::Bounded), None);
+
+        let origin = Hex::from_offset(Point { x: 1, y: 1 });
+        Hexagonal.neighbours(Point { x: 1, y: 1 }, SIZE, Edges::Bounded).iter().for_each(|point| {
+            assert_eq!(Hex::from_offset(*point).distance(origin), 1);
+        });
+    }
+}
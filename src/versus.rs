@@ -0,0 +1,218 @@
+use crate::cell::Mark;
+use crate::game::Game;
+use crate::graphics::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Scoring {
+    /// A point for each mine correctly flagged, like "Minesweeper Flags".
+    /// Finding a mine lets the player go again.
+    Flags,
+    /// A point for each safe cell revealed.
+    Reveals,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Move {
+    Reveal(Point),
+    Flag(Point),
+}
+
+/// Several players taking turns on the same board. Hit mines are flagged
+/// instead of ending the game.
+#[derive(Debug, Clone)]
+pub struct VersusGame {
+    pub game: Game,
+    pub scoring: Scoring,
+    scores: Vec<u32>,
+    current_player: usize,
+}
+
+impl VersusGame {
+    /// Replaces `game.rules.lives`, as hit mines never end a versus match.
+    /// The other rules still apply, and a time or move limit running out
+    /// finishes the match.
+    pub fn new(mut game: Game, players: usize, scoring: Scoring) -> Self {
+        game.rules.lives = u32::MAX;
+        VersusGame {
+            game,
+            scoring,
+            scores: vec![0; players.max(1)],
+            current_player: 0,
+        }
+    }
+
+    pub fn current_player(&self) -> usize {
+        self.current_player
+    }
+
+    pub fn scores(&self) -> &[u32] {
+        &self.scores
+    }
+
+    pub fn is_finished(&self) -> bool {
+        if self.game.is_game_over() {
+            return true;
+        }
+        match self.scoring {
+            Scoring::Flags => self.game.remaining_mines() == 0,
+            Scoring::Reveals => self.safe_cells_left() == 0,
+        }
+    }
+
+    /// Players with the highest score, more than one on a tie.
+    pub fn leaders(&self) -> Vec<usize> {
+        let best = self.scores.iter().max().copied().unwrap_or(0);
+        (0..self.scores.len()).filter(|player| self.scores[*player] == best).collect()
+    }
+
+    /// Plays a move for the current player. Returns false, without
+    /// changing turns, if the move is not allowed.
+    pub fn play(&mut self, next: Move) -> bool {
+        let coordinates = match next {
+            Move::Reveal(coordinates) | Move::Flag(coordinates) => coordinates,
+        };
+        let Some(cell) = self.game.board.cell_at(coordinates).copied() else {
+            return false;
+        };
+        if self.is_finished() || cell.cleared {
+            return false;
+        }
+
+        match (next, self.scoring) {
+            (Move::Flag(_), Scoring::Flags) if cell.mark != Mark::None => false,
+            (Move::Flag(_), Scoring::Flags) if cell.is_mine() => {
+                let flagged = self.game.mark(coordinates, Mark::Flag(cell.mines));
+                if flagged {
+                    self.scores[self.current_player] += cell.mines as u32;
+                }
+                flagged
+            }
+            (Move::Flag(_), Scoring::Flags) => {
+                self.game.selected_at(coordinates);
+                self.next_turn();
+                true
+            }
+            (Move::Flag(_), Scoring::Reveals) => self.game.toggle_flagged(coordinates),
+            (Move::Reveal(_), _) if cell.is_flagged() => false,
+            (Move::Reveal(_), scoring) => {
+                let safe_cells_left = self.safe_cells_left();
+                self.game.selected_at(coordinates);
+                // Refused by the game, like a reveal over the move limit.
+                if !self.game.board.cell_at(coordinates).is_some_and(|cell| cell.cleared) {
+                    return false;
+                }
+                if scoring == Scoring::Reveals {
+                    self.scores[self.current_player] += safe_cells_left - self.safe_cells_left();
+                }
+                self.next_turn();
+                true
+            }
+        }
+    }
+
+    fn next_turn(&mut self) {
+        self.current_player = (self.current_player + 1) % self.scores.len();
+    }
+
+    fn safe_cells_left(&self) -> u32 {
+        let mut count = 0;
+        self.game.board.for_each_cell(|_, cell, _| {
+            if !cell.is_mine() && !cell.cleared {
+                count += 1;
+            }
+        });
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::game::Difficulty;
+    use crate::rules::{FlagLimit, Rules};
+
+    fn game(text: &str) -> Game {
+        Game::new_with_board(Board::from_text(text).unwrap(), Difficulty::Easy)
+    }
+
+    #[test]
+    fn test_flags_scoring() {
+        let mut versus = VersusGame::new(game("*..\n...\n.**"), 2, Scoring::Flags);
+
+        assert!(versus.play(Move::Flag(Point::zero())));
+        assert_eq!(versus.current_player(), 0);
+        assert!(!versus.play(Move::Flag(Point::zero())));
+
+        // A wrong flag reveals the cell and ends the turn.
+        assert!(versus.play(Move::Flag(Point { x: 0, y: 2 })));
+        assert!(versus.game.board.cell_at(Point { x: 1, y: 1 }).unwrap().cleared);
+        assert_eq!(versus.current_player(), 1);
+        assert!(!versus.play(Move::Reveal(Point { x: 1, y: 1 })));
+
+        assert!(versus.play(Move::Flag(Point { x: 2, y: 1 })));
+        assert!(versus.play(Move::Reveal(Point { x: 2, y: 0 })));
+        assert_eq!(versus.current_player(), 0);
+
+        assert!(versus.play(Move::Flag(Point { x: 2, y: 2 })));
+        assert_eq!(versus.scores(), &[2, 1]);
+        assert!(versus.is_finished());
+        assert_eq!(versus.leaders(), vec![0]);
+        assert!(!versus.play(Move::Reveal(Point { x: 1, y: 0 })));
+    }
+
+    #[test]
+    fn test_reveals_scoring() {
+        let mut versus = VersusGame::new(game("....\n....\n...*"), 3, Scoring::Reveals);
+
+        assert!(versus.play(Move::Flag(Point { x: 2, y: 3 })));
+        assert_eq!(versus.current_player(), 0);
+        assert!(!versus.play(Move::Reveal(Point { x: 2, y: 3 })));
+        assert!(versus.play(Move::Flag(Point { x: 2, y: 3 })));
+
+        assert!(versus.play(Move::Reveal(Point { x: 2, y: 2 })));
+        assert!(versus.play(Move::Reveal(Point { x: 2, y: 3 })));
+        assert!(!versus.game.is_game_over());
+        assert!(versus.play(Move::Reveal(Point::zero())));
+
+        assert_eq!(versus.scores(), &[1, 0, 10]);
+        assert_eq!(versus.current_player(), 0);
+        assert!(versus.is_finished());
+        assert_eq!(versus.leaders(), vec![2]);
+    }
+
+    #[test]
+    fn test_multi_mine_flags() {
+        let mut versus = VersusGame::new(game("2..\n...\n..*"), 2, Scoring::Flags);
+
+        assert!(versus.play(Move::Flag(Point::zero())));
+        assert_eq!(versus.game.board.cell_at(Point::zero()).unwrap().mark, Mark::Flag(2));
+        assert!(!versus.is_finished());
+        assert!(versus.play(Move::Flag(Point { x: 2, y: 2 })));
+
+        assert_eq!(versus.scores(), &[3, 0]);
+        assert!(versus.is_finished());
+    }
+
+    #[test]
+    fn test_rules_in_versus() {
+        let rules = Rules {
+            flag_limit: FlagLimit::Count(1),
+            move_limit: Some(2),
+            ..Rules::default()
+        };
+        let mut versus = VersusGame::new(game("*.*\n...\n*.*").with_rules(rules), 2, Scoring::Flags);
+
+        assert!(versus.play(Move::Flag(Point::zero())));
+        assert!(!versus.play(Move::Flag(Point { x: 0, y: 2 })));
+        assert_eq!(versus.scores(), &[1, 0]);
+
+        assert!(versus.play(Move::Reveal(Point { x: 0, y: 1 })));
+        assert!(versus.play(Move::Reveal(Point { x: 1, y: 0 })));
+        assert!(!versus.is_finished());
+        assert!(!versus.play(Move::Reveal(Point { x: 1, y: 1 })));
+        assert_eq!(versus.current_player(), 0);
+        assert!(versus.is_finished());
+        assert!(!versus.play(Move::Reveal(Point { x: 1, y: 2 })));
+    }
+}
@@ -0,0 +1,186 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::board::Board;
+use crate::cell::{Cell, Mark};
+use crate::graphics::*;
+
+/// What a player is allowed to know about a cell.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VisibleCell {
+    Hidden,
+    Flagged(u8),
+    Questioned,
+    Revealed(i8),
+    Mine,
+    /// Outside of the board shape.
+    Void,
+}
+
+impl From<&Cell> for VisibleCell {
+    fn from(cell: &Cell) -> Self {
+        if !cell.cleared {
+            return match cell.mark {
+                Mark::None => VisibleCell::Hidden,
+                Mark::Flag(flags) => VisibleCell::Flagged(flags),
+                Mark::Question => VisibleCell::Questioned,
+            };
+        }
+        if cell.is_mine() {
+            VisibleCell::Mine
+        } else {
+            VisibleCell::Revealed(cell.number)
+        }
+    }
+}
+
+/// Projection of a `Board` that only contains the information shown to the
+/// player, safe to hand over to clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerView {
+    cells: Vec2<VisibleCell>,
+}
+
+impl PlayerView {
+    pub fn new(board: &Board) -> Self {
+        let mut cells = board.cells.map(|cell| VisibleCell::from(cell));
+        cells.for_each_element_mut(|point, cell, _| {
+            if !board.contains(point) {
+                *cell = VisibleCell::Void;
+            }
+        });
+        PlayerView { cells }
+    }
+
+    pub fn cell_at(&self, coordinates: Point) -> Option<VisibleCell> {
+        self.cells.get_element(coordinates).copied()
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.cells.get_width()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.cells.get_height()
+    }
+
+    pub fn get_size(&self) -> Size {
+        Size {
+            width: self.get_width(),
+            height: self.get_height(),
+        }
+    }
+
+    pub fn for_each_cell(&self, f: impl FnMut(Point, &VisibleCell, &mut bool)) {
+        self.cells.for_each_element(f);
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[VisibleCell]> {
+        self.cells.data.iter().map(|row| row.as_slice())
+    }
+
+    /// Cells that are different in `newer`, with their new value.
+    pub fn diff(&self, newer: &PlayerView) -> Vec<(Point, VisibleCell)> {
+        let mut changes = vec![];
+        newer.for_each_cell(|point, cell, _| {
+            if self.cell_at(point) != Some(*cell) {
+                changes.push((point, *cell));
+            }
+        });
+        changes
+    }
+}
+
+impl From<&Board> for PlayerView {
+    fn from(board: &Board) -> Self {
+        PlayerView::new(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_hides_unrevealed_cells() {
+        let mut board = Board::new_empty(Size { width: 3, height: 3 });
+        let mine_coordinates = Point { x: 0, y: 0 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        board.replace_cell(
+            Cell {
+                mark: Mark::Flag(1),
+                ..*board.cell_at(mine_coordinates).unwrap()
+            },
+            mine_coordinates,
+        );
+        board.replace_cell(
+            Cell {
+                cleared: true,
+                ..*board.cell_at(Point { x: 1, y: 1 }).unwrap()
+            },
+            Point { x: 1, y: 1 },
+        );
+
+        let view = board.player_view();
+
+        assert_eq!(view.get_size(), board.get_size());
+        assert_eq!(view.cell_at(mine_coordinates), Some(VisibleCell::Flagged(1)));
+        assert_eq!(view.cell_at(Point { x: 1, y: 1 }), Some(VisibleCell::Revealed(1)));
+        assert_eq!(view.cell_at(Point { x: 2, y: 2 }), Some(VisibleCell::Hidden));
+        assert_eq!(view.cell_at(Point { x: 3, y: 3 }), None);
+
+        let mut hidden = 0;
+        view.for_each_cell(|_, cell, _| {
+            if *cell == VisibleCell::Hidden {
+                hidden += 1;
+            }
+        });
+        assert_eq!(hidden, 7);
+        assert_eq!(view.rows().count(), 3);
+    }
+
+    #[test]
+    fn test_view_shows_cleared_mines() {
+        let mut board = Board::new_empty(Size { width: 2, height: 2 });
+        let mine = Cell {
+            cleared: true,
+            ..Cell::new_mine(Point::zero())
+        };
+        board.replace_cell(mine, Point::zero());
+
+        assert_eq!(board.player_view().cell_at(Point::zero()), Some(VisibleCell::Mine));
+    }
+
+    #[test]
+    fn test_view_masked_cells() {
+        let board = Board::from_text("#.\n.*").unwrap();
+        let view = board.player_view();
+
+        assert_eq!(view.cell_at(Point::zero()), Some(VisibleCell::Void));
+        assert_eq!(view.cell_at(Point { x: 0, y: 1 }), Some(VisibleCell::Hidden));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_view_serialization() {
+        let mut board = Board::new_empty(Size { width: 4, height: 4 });
+        let mine_coordinates = Point { x: 3, y: 3 };
+        board.replace_cell(Cell::new_mine(mine_coordinates), mine_coordinates);
+        board.add_cell_numbers();
+        board.replace_cell(
+            Cell {
+                cleared: true,
+                ..*board.cell_at(Point::zero()).unwrap()
+            },
+            Point::zero(),
+        );
+        let view = board.player_view();
+
+        let json = serde_json::to_string(&view).unwrap();
+        assert!(!json.contains("Mine"));
+        assert_eq!(serde_json::from_str::<PlayerView>(&json).unwrap(), view);
+    }
+}
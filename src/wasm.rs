@@ -0,0 +1,206 @@
+//! JavaScript bindings, built with `--features wasm`.
+//!
+//! ```js
+//! const game = new Game(Difficulty.Easy, BigInt(Date.now()));
+//! game.selectedAt(0, 0);
+//! const cells = game.view(); // row by row
+//! ```
+
+use std::sync::Arc;
+
+use wasm_bindgen::prelude::*;
+
+use crate::board::Board;
+use crate::clock::Clock;
+use crate::game::{self, Difficulty};
+use crate::graphics::Point;
+use crate::rng::SeededRng;
+use crate::view::VisibleCell;
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GameState {
+    Playing,
+    Won,
+    Lost,
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CellState {
+    Hidden,
+    Flagged,
+    Questioned,
+    Revealed,
+    Mine,
+    Void,
+}
+
+/// What the player sees of a cell. `number` is the count of neighbouring
+/// mines for revealed cells and the number of flags for flagged ones.
+#[wasm_bindgen]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CellView {
+    pub state: CellState,
+    pub number: i8,
+}
+
+impl From<VisibleCell> for CellView {
+    fn from(cell: VisibleCell) -> Self {
+        let (state, number) = match cell {
+            VisibleCell::Hidden => (CellState::Hidden, 0),
+            VisibleCell::Flagged(flags) => (CellState::Flagged, flags as i8),
+            VisibleCell::Questioned => (CellState::Questioned, 0),
+            VisibleCell::Revealed(number) => (CellState::Revealed, number),
+            VisibleCell::Mine => (CellState::Mine, 0),
+            VisibleCell::Void => (CellState::Void, 0),
+        };
+        CellView { state, number }
+    }
+}
+
+/// `Instant` is not available on wasm32-unknown-unknown.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug)]
+struct DateClock;
+
+#[cfg(target_arch = "wasm32")]
+impl Clock for DateClock {
+    fn now(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+    }
+}
+
+fn clock() -> Arc<dyn Clock> {
+    #[cfg(target_arch = "wasm32")]
+    return Arc::new(DateClock);
+    #[cfg(not(target_arch = "wasm32"))]
+    Arc::new(crate::clock::SystemClock::default())
+}
+
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct Game {
+    game: game::Game,
+}
+
+#[wasm_bindgen]
+impl Game {
+    /// Places the mines from `seed`, so JavaScript decides the randomness.
+    #[wasm_bindgen(constructor)]
+    pub fn new(difficulty: Difficulty, seed: u64) -> Game {
+        let game = game::Game::new_with_rng(difficulty, &mut SeededRng::new(seed));
+        Game { game: game.with_clock(clock()) }
+    }
+
+    /// Game on a fixed layout, in the `Board::from_text` format.
+    #[wasm_bindgen(js_name = fromText)]
+    pub fn from_text(text: &str) -> Option<Game> {
+        let board = Board::from_text(text)?;
+        let game = game::Game::new_with_board(board, Difficulty::Easy);
+        Some(Game { game: game.with_clock(clock()) })
+    }
+
+    pub fn width(&self) -> usize {
+        self.game.board.get_width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.game.board.get_height()
+    }
+
+    /// Flagged cells are left hidden, like in `Session` and `VersusGame`.
+    #[wasm_bindgen(js_name = selectedAt)]
+    pub fn selected_at(&mut self, x: usize, y: usize) -> Option<CellView> {
+        let coordinates = Point { x, y };
+        if !self.game.board.cell_at(coordinates)?.is_flagged() {
+            self.game.selected_at(coordinates);
+        }
+        self.cell_at(x, y)
+    }
+
+    #[wasm_bindgen(js_name = toggleFlagged)]
+    pub fn toggle_flagged(&mut self, x: usize, y: usize) -> bool {
+        self.game.toggle_flagged(Point { x, y })
+    }
+
+    #[wasm_bindgen(js_name = cycleMark)]
+    pub fn cycle_mark(&mut self, x: usize, y: usize) -> bool {
+        self.game.cycle_mark(Point { x, y })
+    }
+
+    /// Returns how many cells were revealed.
+    #[wasm_bindgen(js_name = chordAt)]
+    pub fn chord_at(&mut self, x: usize, y: usize) -> usize {
+        self.game.chord_at(Point { x, y }).len()
+    }
+
+    pub fn state(&self) -> GameState {
+        if self.game.is_game_over() {
+            GameState::Lost
+        } else if self.game.is_win() {
+            GameState::Won
+        } else {
+            GameState::Playing
+        }
+    }
+
+    #[wasm_bindgen(js_name = flagsRemaining)]
+    pub fn flags_remaining(&self) -> i32 {
+        self.game.flags_remaining()
+    }
+
+    #[wasm_bindgen(js_name = elapsedSeconds)]
+    pub fn elapsed_seconds(&self) -> f64 {
+        self.game.elapsed().as_secs_f64()
+    }
+
+    #[wasm_bindgen(js_name = cellAt)]
+    pub fn cell_at(&self, x: usize, y: usize) -> Option<CellView> {
+        self.game.board.player_view().cell_at(Point { x, y }).map(CellView::from)
+    }
+
+    /// Snapshot of the player view, row by row.
+    pub fn view(&self) -> Vec<CellView> {
+        self.game.player_view().rows().flatten().map(|cell| CellView::from(*cell)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_games() {
+        let mut first = Game::new(Difficulty::Medium, 7);
+        let mut second = Game::new(Difficulty::Medium, 7);
+        assert_eq!(first.game.board.to_text(), second.game.board.to_text());
+        assert_ne!(first.game.board.to_text(), Game::new(Difficulty::Medium, 8).game.board.to_text());
+
+        first.selected_at(5, 5);
+        second.selected_at(5, 5);
+        assert_eq!(first.view(), second.view());
+        assert_eq!(first.view().len(), 16 * 16);
+    }
+
+    #[test]
+    fn test_playing() {
+        let mut game = Game::from_text("*..\n...\n...").unwrap();
+        assert_eq!((game.width(), game.height()), (3, 3));
+
+        assert_eq!(
+            game.selected_at(1, 1),
+            Some(CellView {
+                state: CellState::Revealed,
+                number: 1
+            })
+        );
+        assert!(game.toggle_flagged(0, 0));
+        assert_eq!(game.selected_at(0, 0).unwrap().state, CellState::Flagged);
+        assert_eq!(game.flags_remaining(), 0);
+        assert_eq!(game.chord_at(1, 1), 7);
+        assert_eq!(game.state(), GameState::Won);
+        assert_eq!(game.cell_at(0, 0).unwrap().state, CellState::Flagged);
+        assert_eq!(game.cell_at(3, 0), None);
+    }
+}
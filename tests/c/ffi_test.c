@@ -0,0 +1,71 @@
+/* Exercises the C ABI. Built and run by CI, see .github/workflows/ci.yml. */
+
+#include <assert.h>
+#include <stdio.h>
+
+#include "minesweeper_core.h"
+
+static void test_scripted_game(void) {
+  MsGameHandle *game = ms_game_from_text("*..\n...\n...");
+  assert(game != NULL);
+  assert(ms_game_width(game) == 3 && ms_game_height(game) == 3);
+
+  assert(ms_game_reveal(game, 3, 0) == MS_STATUS_OUT_OF_BOUNDS);
+  assert(ms_game_reveal(game, 2, 2) == MS_STATUS_OK);
+  assert(ms_game_state(game) == MS_GAME_STATE_PLAYING);
+
+  MsCellInfo cell;
+  assert(ms_game_cell(game, 1, 1, &cell) == MS_STATUS_OK);
+  assert(cell.state == MS_CELL_STATE_REVEALED && cell.number == 1);
+  assert(ms_game_cell(game, 0, 0, &cell) == MS_STATUS_OK);
+  assert(cell.state == MS_CELL_STATE_HIDDEN);
+
+  assert(ms_game_flag(game, 1, 1) == MS_STATUS_REJECTED);
+  assert(ms_game_flag(game, 0, 0) == MS_STATUS_OK);
+  assert(ms_game_flags_remaining(game) == 0);
+  assert(ms_game_state(game) == MS_GAME_STATE_WON);
+  assert(ms_game_chord(game, 1, 1) == MS_STATUS_GAME_OVER);
+
+  ms_game_free(game);
+}
+
+static void test_lost_game(void) {
+  MsGameHandle *game = ms_game_new_custom(8, 8, 63, 7);
+  assert(game != NULL);
+
+  MsStatus status = MS_STATUS_OK;
+  for (size_t x = 0; x < 8 && ms_game_state(game) == MS_GAME_STATE_PLAYING; x++) {
+    status = ms_game_reveal(game, x, 0);
+  }
+  assert(status == MS_STATUS_OK);
+  assert(ms_game_state(game) == MS_GAME_STATE_LOST);
+  assert(ms_game_reveal(game, 7, 7) == MS_STATUS_GAME_OVER);
+
+  ms_game_free(game);
+}
+
+static void test_invalid_arguments(void) {
+  assert(ms_game_new_custom(0, 4, 1, 0) == NULL);
+  assert(ms_game_from_text("*.\n...") == NULL);
+  assert(ms_game_new_custom(SIZE_MAX, 2, 1, 0) == NULL);
+  assert(ms_game_new_custom(1 << 16, 1 << 16, 1, 0) == NULL);
+  assert(ms_game_new(42) == NULL);
+  assert(ms_game_reveal(NULL, 0, 0) == MS_STATUS_NULL_POINTER);
+  assert(ms_game_state(NULL) == MS_GAME_STATE_INVALID);
+  ms_game_free(NULL);
+
+  MsGameHandle *game = ms_game_new(MS_DIFFICULTY_HARD);
+  assert(ms_game_flags_remaining(game) == 99);
+  assert(ms_game_flag(game, 0, 0) == MS_STATUS_OK);
+  assert(ms_game_reveal(game, 0, 0) == MS_STATUS_REJECTED);
+  assert(ms_game_cell(game, 0, 0, NULL) == MS_STATUS_NULL_POINTER);
+  ms_game_free(game);
+}
+
+int main(void) {
+  test_scripted_game();
+  test_lost_game();
+  test_invalid_arguments();
+  puts("ffi tests passed");
+  return 0;
+}
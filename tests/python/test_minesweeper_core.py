@@ -0,0 +1,74 @@
+import pytest
+
+import minesweeper_core
+from minesweeper_core import Board, Game
+
+
+def test_seeded_boards():
+    board = Board.generate(16, 30, 99, seed=3)
+
+    assert board.to_text() == Board.generate(16, 30, 99, seed=3).to_text()
+    assert board.to_text() != Board.generate(16, 30, 99, seed=4).to_text()
+    assert (board.width, board.height) == (16, 30)
+    assert sum(map(sum, board.mines())) == 99
+    assert board.bbbv() > 0
+
+
+def test_board_numbers():
+    board = Board.from_text("*..\n...\n..*")
+
+    assert board.numbers() == [[0, 1, 0], [1, 2, 1], [0, 1, 0]]
+    assert str(board) == "*..\n...\n..*"
+    with pytest.raises(ValueError):
+        Board.from_text("*.\n...")
+    with pytest.raises(ValueError):
+        Board.generate(2, 2, 4)
+    with pytest.raises(ValueError):
+        Board.generate(2**63, 4, 1)
+    with pytest.raises(ValueError):
+        Board.generate(2**11, 2**10, 1)
+
+
+def test_dense_boards():
+    board = Board.generate(100, 100, 9999, seed=1)
+
+    assert sum(map(sum, board.mines())) == 9999
+
+
+def test_game_actions():
+    game = Game.from_board(Board.from_text("*..\n...\n..."))
+
+    assert game.selected_at(1, 1) == 1
+    assert not game.toggle_flagged(1, 1)
+    assert game.toggle_flagged(0, 0)
+    assert game.flags_remaining == 0
+    assert game.state == "won"
+    assert game.selected_at(0, 0) == minesweeper_core.FLAGGED
+    assert game.view() == [
+        [minesweeper_core.FLAGGED, minesweeper_core.HIDDEN, minesweeper_core.HIDDEN],
+        [minesweeper_core.HIDDEN, 1, minesweeper_core.HIDDEN],
+        [minesweeper_core.HIDDEN, minesweeper_core.HIDDEN, minesweeper_core.HIDDEN],
+    ]
+    assert game.statistics() == {"left_clicks": 1, "right_clicks": 2, "chords": 0, "wasted_clicks": 1}
+
+
+def test_lost_game_metrics():
+    game = Game.from_board(Board.from_text("*..\n...\n..*"))
+    game.selected_at(1, 0)
+    game.selected_at(0, 0)
+
+    assert game.state == "lost"
+    assert game.view()[0][0] == minesweeper_core.MINE
+    assert game.efficiency(2.0)["bbbv"] == game.board.bbbv()
+
+
+def test_view_is_numpy_friendly():
+    np = pytest.importorskip("numpy")
+    game = Game("medium", seed=7)
+    game.selected_at(8, 8)
+
+    cells = np.array(game.view())
+    assert cells.shape == (16, 16)
+    assert (cells != minesweeper_core.HIDDEN).any()
+    with pytest.raises(ValueError):
+        Game("impossible")
@@ -0,0 +1,86 @@
+#![cfg(feature = "server")]
+
+use std::io::{BufRead, BufReader, Lines, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+use minesweeper_core::server::{Event, Server, Status};
+use minesweeper_core::{Outcome, Point, VisibleCell};
+
+struct Client {
+    stream: TcpStream,
+    lines: Lines<BufReader<TcpStream>>,
+}
+
+impl Client {
+    fn connect(address: &str) -> Client {
+        let stream = TcpStream::connect(address).unwrap();
+        let lines = BufReader::new(stream.try_clone().unwrap()).lines();
+        Client { stream, lines }
+    }
+
+    fn send(&mut self, line: &str) {
+        writeln!(self.stream, "{line}").unwrap();
+    }
+
+    fn receive(&mut self) -> Event {
+        let line = self.lines.next().unwrap().unwrap();
+        assert!(!line.contains("mines"), "hidden state leaked: {line}");
+        serde_json::from_str(&line).unwrap()
+    }
+}
+
+fn start_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || Arc::new(Server::new()).serve(listener));
+    address
+}
+
+#[test]
+fn test_scripted_clients() {
+    let address = start_server();
+    let mut alice = Client::connect(&address);
+    let mut bob = Client::connect(&address);
+
+    alice.send(r#"{"command":"create","difficulty":"easy"}"#);
+    let Event::Created { game } = alice.receive() else { panic!() };
+    let Event::State { view, status, .. } = alice.receive() else { panic!() };
+    assert_eq!(status, Status::Playing);
+    assert_eq!(view.get_size(), minesweeper_core::Size { width: 10, height: 10 });
+    view.for_each_cell(|_, cell, _| assert_eq!(*cell, VisibleCell::Hidden));
+
+    alice.send(&format!(r#"{{"command":"flag","game":{game},"x":0,"y":0}}"#));
+    let Event::Delta { delta, .. } = alice.receive() else { panic!() };
+    assert_eq!(delta.outcome, Outcome::Applied);
+    assert_eq!(delta.changes, vec![(Point::zero(), VisibleCell::Flagged(1))]);
+
+    bob.send(&format!(r#"{{"command":"subscribe","game":{game}}}"#));
+    let Event::State { view, .. } = bob.receive() else { panic!() };
+    assert_eq!(view.cell_at(Point::zero()), Some(VisibleCell::Flagged(1)));
+
+    alice.send(&format!(r#"{{"command":"reveal","game":{game},"x":9,"y":9}}"#));
+    let sent = alice.receive();
+    assert_eq!(bob.receive(), sent);
+    let Event::Delta { delta, .. } = sent else { panic!() };
+    assert!(!delta.changes.is_empty());
+    assert!(delta.changes.iter().all(|(_, cell)| *cell != VisibleCell::Hidden));
+}
+
+#[test]
+fn test_invalid_commands() {
+    let address = start_server();
+    let mut client = Client::connect(&address);
+
+    client.send("not json");
+    assert!(matches!(client.receive(), Event::Error { .. }));
+
+    client.send(r#"{"command":"reveal","game":7,"x":0,"y":0}"#);
+    assert_eq!(
+        client.receive(),
+        Event::Error {
+            message: "unknown game 7".to_string()
+        }
+    );
+}
@@ -0,0 +1,26 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use minesweeper_core::wasm::{CellState, Game, GameState};
+use minesweeper_core::Difficulty;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_seeded_game() {
+    let mut game = Game::new(Difficulty::Easy, 42);
+    let same = Game::new(Difficulty::Easy, 42);
+    assert_eq!(game.view(), same.view());
+
+    game.selected_at(0, 0);
+    assert_ne!(game.cell_at(0, 0).unwrap().state, CellState::Hidden);
+    assert!(game.elapsed_seconds() >= 0.0);
+}
+
+#[wasm_bindgen_test]
+fn test_lost_game() {
+    let mut game = Game::from_text("*.\n..").unwrap();
+    assert_eq!(game.chord_at(1, 1), 0);
+
+    game.selected_at(0, 0);
+    assert_eq!(game.state(), GameState::Lost);
+    assert_eq!(game.view()[0].state, CellState::Mine);
+}